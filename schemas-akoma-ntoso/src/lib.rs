@@ -82,4 +82,78 @@ mod tests {
         assert!(summary.file_count > 0);
         assert!(summary.total_size > 0);
     }
+
+    #[test]
+    fn test_contains() {
+        assert!(AkomaNtoso30::contains("akomantoso30.xsd"));
+        assert!(!AkomaNtoso30::contains("akomantoso31.xsd"));
+    }
+
+    fn decode_base64(text: &str) -> Vec<u8> {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut out = Vec::new();
+        let (mut acc, mut bits) = (0u32, 0);
+        for c in text.bytes().take_while(|&c| c != b'=') {
+            acc = (acc << 6) | ALPHABET.iter().position(|&a| a == c).unwrap() as u32;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                out.push((acc >> bits) as u8);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_to_base64_json() {
+        let json = AkomaNtoso30::to_base64_json();
+        assert!(json.starts_with(r#"[{"path":"akomantoso30.xsd","content_b64":""#));
+
+        let record = json
+            .split(r#""path":"xml.xsd","content_b64":""#)
+            .nth(1)
+            .unwrap();
+        let encoded = &record[..record.find('"').unwrap()];
+        assert_eq!(
+            decode_base64(encoded),
+            AkomaNtoso30::get_file("xml.xsd").unwrap().contents()
+        );
+    }
+
+    #[test]
+    fn test_primary_schema_report() {
+        let report = AkomaNtoso30::primary_schema_report().unwrap();
+        assert_eq!(report.path, "akomantoso30.xsd");
+        assert_eq!(
+            report.target_namespace,
+            Some("http://docs.oasis-open.org/legaldocml/ns/akn/3.0")
+        );
+        assert_eq!(report.root_element, "schema");
+    }
+
+    #[test]
+    fn test_complexity_ranking() {
+        let ranking = AkomaNtoso30::complexity_ranking();
+        assert_eq!(ranking[0].0, "akomantoso30.xsd");
+        assert_eq!(
+            ranking[0].0,
+            AkomaNtoso30::largest_file()
+                .unwrap()
+                .path()
+                .to_str()
+                .unwrap()
+        );
+        assert!(ranking.windows(2).all(|w| w[0].1 >= w[1].1));
+        assert!(ranking.iter().all(|(_, score)| (0.0..=3.0).contains(score)));
+    }
+
+    #[test]
+    fn test_path_for_content() {
+        let contents = AkomaNtoso30::get_file("xml.xsd").unwrap().contents();
+        assert_eq!(AkomaNtoso30::path_for_content(contents), Some("xml.xsd"));
+
+        let copy = contents.to_vec();
+        assert_eq!(AkomaNtoso30::path_for_content(&copy), Some("xml.xsd"));
+        assert_eq!(AkomaNtoso30::path_for_content(b"not a schema"), None);
+    }
 }
@@ -52,6 +52,7 @@ impl SchemaBundle for Bits22 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
 
     #[test]
     fn test_file_count() {
@@ -82,4 +83,85 @@ mod tests {
         assert!(summary.file_count > 0);
         assert!(summary.total_size > 0);
     }
+
+    #[test]
+    fn test_pack_round_trip() {
+        let records = schemas_core::unpack(&Bits22::pack()).unwrap();
+        assert_eq!(records.len(), Bits22::file_count());
+        for ((path, contents), file) in records.iter().zip(Bits22::files()) {
+            assert_eq!(Path::new(path), file.path());
+            assert_eq!(contents.as_slice(), file.contents());
+        }
+        assert!(schemas_core::unpack(&Bits22::pack()[..10]).is_err());
+
+        // A tenth length byte carrying more than the top bit overflows u64
+        let overflowing = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+        assert!(matches!(
+            schemas_core::unpack(&overflowing),
+            Err(SchemaError::MalformedPack(msg)) if msg.contains("overflows")
+        ));
+    }
+
+    #[test]
+    fn test_file_reader() {
+        use std::io::Read;
+
+        let path = "BITS-book2-2.xsd";
+        let mut contents = Vec::new();
+        Bits22::file_reader(path)
+            .unwrap()
+            .read_to_end(&mut contents)
+            .unwrap();
+        assert_eq!(contents, Bits22::get_file(path).unwrap().contents());
+        assert!(Bits22::file_reader("missing.xsd").is_none());
+    }
+
+    #[test]
+    fn test_tree_nodes() {
+        let nodes = Bits22::tree_nodes();
+
+        let mut dirs = std::collections::BTreeSet::new();
+        for path in Bits22::list_paths() {
+            dirs.extend(
+                path.ancestors()
+                    .skip(1)
+                    .filter(|a| !a.as_os_str().is_empty()),
+            );
+        }
+        assert_eq!(nodes.len(), Bits22::file_count() + dirs.len());
+        assert_eq!(nodes.iter().filter(|n| n.is_dir).count(), dirs.len());
+
+        // Depth only increases by one, and only into a directory's children
+        assert!(nodes.first().is_some_and(|n| n.depth == 0));
+        for pair in nodes.windows(2) {
+            assert!(pair[1].depth <= pair[0].depth + 1);
+            if pair[1].depth > pair[0].depth {
+                assert!(pair[0].is_dir);
+                assert!(pair[1].path.starts_with(&pair[0].path));
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_to_directory_readonly() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let base =
+            std::env::temp_dir().join(format!("schemas-bits-readonly-{}", std::process::id()));
+        let count = Bits22::write_to_directory_readonly(&base).unwrap();
+        assert_eq!(count, Bits22::file_count());
+
+        // Extracting again replaces the read-only files
+        assert_eq!(Bits22::write_to_directory_readonly(&base).unwrap(), count);
+
+        for path in Bits22::list_paths() {
+            let mode = std::fs::metadata(base.join(path))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o222, 0, "{} is writable", path.display());
+        }
+        std::fs::remove_dir_all(&base).unwrap();
+    }
 }
@@ -0,0 +1,88 @@
+//! On-disk overrides backing the `hot-reload` feature.
+//!
+//! When an override directory is registered for a bundle, [`get_file`] serves
+//! files found there instead of the embedded copies, re-reading them whenever
+//! their modification time or size changes. Re-read contents are leaked to
+//! satisfy the `'static` lifetime of [`File`], so this is meant for schema
+//! development only, never for production use.
+//!
+//! [`get_file`]: crate::SchemaBundle::get_file
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use crate::File;
+
+/// Identifies a bundle by `(NAME, VERSION)`; the name alone is shared by
+/// DITA 1.2 and 1.3.
+pub(crate) type BundleKey = (&'static str, &'static str);
+
+#[derive(Default)]
+struct State {
+    /// Override directory per bundle.
+    dirs: HashMap<BundleKey, PathBuf>,
+    /// Last loaded version of each overridden file, keyed by bundle and path.
+    cache: HashMap<(BundleKey, String), Cached>,
+}
+
+struct Cached {
+    modified: Option<SystemTime>,
+    len: u64,
+    file: &'static File<'static>,
+}
+
+fn state() -> &'static Mutex<State> {
+    static STATE: OnceLock<Mutex<State>> = OnceLock::new();
+    STATE.get_or_init(Default::default)
+}
+
+/// Register (or with `None`, clear) the override directory for a bundle.
+pub(crate) fn set_override_dir(bundle: BundleKey, dir: Option<PathBuf>) {
+    let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+    state.cache.retain(|(name, _), _| *name != bundle);
+    match dir {
+        Some(dir) => state.dirs.insert(bundle, dir),
+        None => state.dirs.remove(&bundle),
+    };
+}
+
+/// Look up `path` in the bundle's override directory, reloading it if it changed.
+///
+/// Returns `None` when no override directory is set or the file is absent
+/// there, in which case the embedded copy should be used. Paths that are
+/// absolute or contain `.`/`..` segments never leave the override directory
+/// and are not looked up.
+pub(crate) fn lookup(bundle: BundleKey, path: &str) -> Option<&'static File<'static>> {
+    if !Path::new(path)
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return None;
+    }
+    let mut state = state().lock().unwrap_or_else(|e| e.into_inner());
+    let full_path = state.dirs.get(&bundle)?.join(path);
+    let metadata = std::fs::metadata(&full_path).ok().filter(|m| m.is_file())?;
+    let modified = metadata.modified().ok();
+
+    let key = (bundle, path.to_string());
+    if let Some(cached) = state.cache.get(&key) {
+        if cached.modified == modified && cached.len == metadata.len() {
+            return Some(cached.file);
+        }
+    }
+
+    let contents: &'static [u8] = std::fs::read(&full_path).ok()?.leak();
+    let path: &'static str = path.to_string().leak();
+    let file: &'static File<'static> = Box::leak(Box::new(File::new(path, contents)));
+    state.cache.insert(
+        key,
+        Cached {
+            modified,
+            len: metadata.len(),
+            file,
+        },
+    );
+    Some(file)
+}
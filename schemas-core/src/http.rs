@@ -0,0 +1,136 @@
+//! Minimal read-only HTTP server backing the `http-server` feature.
+//!
+//! Built on [`std::net::TcpListener`] with one thread per connection. It only
+//! understands `GET` and `HEAD` for bundle paths and closes each connection
+//! after one response, which is enough for pointing a catalog resolver or a
+//! browser at a bundle during testing. It is not meant for production use.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+
+use crate::{SchemaBundle, content_type};
+
+/// Serve a bundle's files over HTTP on `addr`, blocking forever.
+///
+/// `GET /<path>` returns the file at `<path>` with its [`content_type`];
+/// unknown paths, and paths with empty, `.` or `..` segments after
+/// percent-decoding, get `404 Not Found`.
+pub fn serve_bundle<B: SchemaBundle>(addr: SocketAddr) -> std::io::Result<()> {
+    serve_listener::<B>(TcpListener::bind(addr)?)
+}
+
+/// Serve a bundle on an already bound listener, blocking forever.
+///
+/// Binding port 0 and reading [`TcpListener::local_addr`] before calling this
+/// gives a server on an ephemeral port.
+pub fn serve_listener<B: SchemaBundle>(listener: TcpListener) -> std::io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        std::thread::spawn(move || {
+            // A client hanging up mid-response is not the server's problem
+            let _ = handle::<B>(stream);
+        });
+    }
+    Ok(())
+}
+
+fn handle<B: SchemaBundle>(stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers; none of them affect the response
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or_default(), parts.next());
+    let mut out = &stream;
+    let response = match method {
+        "GET" | "HEAD" => Response {
+            head_only: method == "HEAD",
+            ..Response::default()
+        },
+        _ => {
+            let response = Response {
+                status: "405 Method Not Allowed",
+                extra_headers: "Allow: GET, HEAD\r\n",
+                body: b"",
+                ..Response::default()
+            };
+            return response.write(&mut out);
+        }
+    };
+
+    let path = target
+        .map(|t| t.split(['?', '#']).next().unwrap_or_default())
+        .and_then(|t| percent_decode(t.trim_start_matches('/')))
+        // Traversal segments are refused even if the bundle lookup would allow them
+        .filter(|p| !p.split('/').any(|s| matches!(s, "" | "." | "..")));
+    let response = match path.as_deref().map(|p| (p, B::get_file(p))) {
+        Some((path, Some(file))) => Response {
+            status: "200 OK",
+            content_type: content_type(path),
+            body: file.contents(),
+            ..response
+        },
+        _ => response,
+    };
+    response.write(&mut out)
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    extra_headers: &'static str,
+    body: &'static [u8],
+    head_only: bool,
+}
+
+impl Default for Response {
+    fn default() -> Self {
+        Self {
+            status: "404 Not Found",
+            content_type: "text/plain",
+            extra_headers: "",
+            body: b"not found\n",
+            head_only: false,
+        }
+    }
+}
+
+impl Response {
+    fn write(&self, out: &mut impl Write) -> std::io::Result<()> {
+        write!(
+            out,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+            self.status,
+            self.content_type,
+            self.body.len(),
+            self.extra_headers,
+        )?;
+        if !self.head_only {
+            out.write_all(self.body)?;
+        }
+        out.flush()
+    }
+}
+
+/// Decode `%XX` escapes in a request path, rejecting malformed input.
+fn percent_decode(path: &str) -> Option<String> {
+    let bytes = path.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
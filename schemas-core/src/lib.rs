@@ -3,10 +3,37 @@
 //! This crate provides the [`SchemaBundle`] trait that all schema crates implement,
 //! allowing uniform access to statically embedded schema files.
 
-use std::path::Path;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
 pub use include_dir::{self, Dir, DirEntry, File};
 
+#[cfg(feature = "checksums")]
+mod checksum;
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
+#[cfg(feature = "http-server")]
+mod http;
+#[cfg(feature = "json")]
+mod json;
+mod pack;
+#[cfg(feature = "search")]
+mod search;
+#[cfg(feature = "tar")]
+mod tar;
+#[cfg(feature = "xml")]
+mod xml;
+#[cfg(feature = "zip")]
+mod zip;
+
+#[cfg(feature = "http-server")]
+pub use http::{serve_bundle, serve_listener};
+pub use pack::unpack;
+#[cfg(feature = "xml")]
+pub use xml::{DeclarationCounts, PrimarySchemaReport, SchemaLanguage};
+
 /// Error types for schema operations.
 #[derive(Debug, thiserror::Error)]
 pub enum SchemaError {
@@ -22,6 +49,14 @@ pub enum SchemaError {
         source: std::io::Error,
     },
 
+    /// Failed to read a file or directory.
+    #[error("failed to read {path}: {source}")]
+    ReadError {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
     /// Failed to create a directory.
     #[error("failed to create directory {path}: {source}")]
     CreateDirError {
@@ -29,6 +64,30 @@ pub enum SchemaError {
         #[source]
         source: std::io::Error,
     },
+
+    /// A file's contents are not valid UTF-8.
+    #[error("schema file is not valid UTF-8: {path}")]
+    InvalidUtf8 { path: String },
+
+    /// A document could not be read as XML.
+    #[error("invalid XML document: {0}")]
+    InvalidDocument(String),
+
+    /// No schema in the bundle matches the given namespace and root element.
+    #[error("no schema found for {0}")]
+    NoMatchingSchema(String),
+
+    /// Two files to be written into one directory share a file name.
+    #[error("cannot flatten {first} and {second}: both are named {name}")]
+    FlattenCollision {
+        name: String,
+        first: String,
+        second: String,
+    },
+
+    /// A packed bundle blob could not be decoded.
+    #[error("malformed packed bundle: {0}")]
+    MalformedPack(String),
 }
 
 /// A bundle of schema files that can be accessed and extracted.
@@ -45,6 +104,15 @@ pub trait SchemaBundle {
     /// License identifier (e.g., "OASIS-IPR", "Apache-2.0")
     const LICENSE: &'static str;
 
+    /// Path of the primary schema, for bundles where the
+    /// [`primary_file`](Self::primary_file) heuristic picks the wrong one
+    const PRIMARY_SCHEMA: Option<&'static str> = None;
+
+    /// Bundle paths that schemas reference but the distribution does not ship,
+    /// which [`verify_entry_points`](Self::verify_entry_points) treats as
+    /// external rather than missing
+    const EXTERNAL_REFERENCES: &'static [&'static str] = &[];
+
     /// Get the embedded directory containing all schema files.
     fn dir() -> &'static Dir<'static>;
 
@@ -54,11 +122,129 @@ pub trait SchemaBundle {
     }
 
     /// Find a file by its exact relative path.
+    ///
+    /// With the `hot-reload` feature, a copy in the bundle's override directory
+    /// (see [`set_override_dir`](Self::set_override_dir)) takes precedence.
     fn get_file(path: &str) -> Option<&'static File<'static>> {
+        #[cfg(feature = "hot-reload")]
+        if let Some(file) = hot_reload::lookup((Self::NAME, Self::VERSION), path) {
+            return Some(file);
+        }
         Self::dir().get_file(path)
     }
 
+    /// Get a file's contents as text.
+    ///
+    /// Fails with [`SchemaError::FileNotFound`] for unknown paths and
+    /// [`SchemaError::InvalidUtf8`] when the contents are not UTF-8.
+    fn get_file_str(path: &str) -> Result<&'static str, SchemaError> {
+        Self::get_file(path)
+            .ok_or_else(|| SchemaError::FileNotFound(path.to_string()))?
+            .contents_utf8()
+            .ok_or_else(|| SchemaError::InvalidUtf8 {
+                path: path.to_string(),
+            })
+    }
+
+    /// Find the path of the file holding `content`.
+    ///
+    /// A slice borrowed from an embedded file is matched by address through an
+    /// index built on first use, which tells apart identical copies and never
+    /// compares bytes. Any other slice falls back to a linear byte comparison,
+    /// returning the smallest matching path.
+    fn path_for_content(content: &[u8]) -> Option<&'static str> {
+        if let Some(path) = path_by_address(Self::dir(), content) {
+            return Some(path);
+        }
+        Self::files()
+            .filter(|f| f.contents() == content)
+            .filter_map(|f| f.path().to_str())
+            .min()
+    }
+
+    /// Get a reader over a file's embedded bytes.
+    ///
+    /// The cursor borrows the static contents without copying. Besides
+    /// [`std::io::Read`], `Cursor<&[u8]>` implements `tokio::io::AsyncRead`, so
+    /// it can be streamed from async handlers without blocking or an extra
+    /// dependency here.
+    fn file_reader(path: &str) -> Option<std::io::Cursor<&'static [u8]>> {
+        Self::get_file(path).map(|f| std::io::Cursor::new(f.contents()))
+    }
+
+    /// Paths that collide with another file's path, sorted.
+    ///
+    /// Comparison ignores ASCII case, since such paths overwrite each other when
+    /// extracted on case-insensitive filesystems. A well-formed bundle returns
+    /// an empty list, which keeps [`get_file`](Self::get_file) unambiguous.
+    fn duplicate_paths() -> Vec<&'static str> {
+        let mut by_key: BTreeMap<String, Vec<&'static str>> = BTreeMap::new();
+        for path in Self::list_paths().filter_map(Path::to_str) {
+            by_key
+                .entry(path.to_ascii_lowercase())
+                .or_default()
+                .push(path);
+        }
+        let mut duplicates: Vec<_> = by_key
+            .into_values()
+            .filter(|paths| paths.len() > 1)
+            .flatten()
+            .collect();
+        duplicates.sort_unstable();
+        duplicates
+    }
+
+    /// Relative path of the license file embedded in the bundle, if any.
+    ///
+    /// Recognises `LICENSE`, `LICENCE`, `COPYING` and `NOTICE` files (any case,
+    /// optionally with a `.txt` or `.md` extension), preferring the one
+    /// closest to the bundle root.
+    fn license_file_path() -> Option<&'static str> {
+        Self::list_paths()
+            .filter(|path| is_license_file(path))
+            .filter_map(Path::to_str)
+            .min_by_key(|path| (path.matches('/').count(), *path))
+    }
+
+    /// Text of the license file embedded in the bundle, if any.
+    ///
+    /// See [`license_file_path`](Self::license_file_path) for how it is found.
+    fn license_text() -> Option<&'static str> {
+        Self::get_file(Self::license_file_path()?)?.contents_utf8()
+    }
+
+    /// Check whether the bundle contains a file at `path`.
+    ///
+    /// Uses the same lookup as [`get_file`](Self::get_file).
+    fn contains(path: &str) -> bool {
+        Self::get_file(path).is_some()
+    }
+
+    /// Serve files from `dir` in preference to the embedded copies, or stop
+    /// doing so with `None`.
+    ///
+    /// Only lookups through [`get_file`](Self::get_file) see overrides, and
+    /// changed files are picked up on the next lookup. Intended for schema
+    /// development: every reload leaks the previous contents.
+    #[cfg(feature = "hot-reload")]
+    fn set_override_dir(dir: Option<PathBuf>) {
+        hot_reload::set_override_dir((Self::NAME, Self::VERSION), dir);
+    }
+
+    /// Look up several files at once.
+    ///
+    /// Results are positionally aligned with `paths`, with `None` for paths
+    /// not in the bundle.
+    fn get_files(paths: &[&str]) -> Vec<Option<&'static File<'static>>> {
+        paths.iter().map(|path| Self::get_file(path)).collect()
+    }
+
     /// Get all files recursively as an iterator.
+    ///
+    /// By default each directory's files come before its subdirectories, as
+    /// laid out by `include_dir`. With the `sorted-default` feature, files are
+    /// yielded in sorted relative-path order instead, which makes every
+    /// iterator built on this one deterministic across platforms.
     fn files() -> impl Iterator<Item = &'static File<'static>> {
         all_files(Self::dir()).into_iter()
     }
@@ -78,6 +264,13 @@ pub trait SchemaBundle {
         Self::files().map(|f| f.path())
     }
 
+    /// All relative file paths as owned `PathBuf`s, sorted.
+    fn relative_paths() -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = Self::list_paths().map(Path::to_path_buf).collect();
+        paths.sort();
+        paths
+    }
+
     /// Write all schema files to the given base directory.
     ///
     /// This creates the directory structure and writes all files,
@@ -108,10 +301,1120 @@ pub trait SchemaBundle {
         Ok(count)
     }
 
+    /// Write all files as a reproducible tar archive, suitable as an OCI layer.
+    ///
+    /// Entries are regular files in sorted path order with mtime 0, uid/gid 0
+    /// and mode `0644`, so the output (and a layer digest over it) only
+    /// changes when the bundle does. Parent directories are left implicit.
+    /// Returns the number of files written.
+    #[cfg(feature = "tar")]
+    fn write_reproducible_tar<W: std::io::Write>(mut writer: W) -> Result<usize, SchemaError> {
+        let mut files: Vec<_> = Self::files().collect();
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+
+        for file in &files {
+            let path = file.path().to_string_lossy();
+            tar::append_file(&mut writer, &path, file.contents()).map_err(|e| {
+                SchemaError::WriteError {
+                    path: path.to_string(),
+                    source: e,
+                }
+            })?;
+        }
+        tar::finish(&mut writer).map_err(|e| SchemaError::WriteError {
+            path: "tar archive".to_string(),
+            source: e,
+        })?;
+        Ok(files.len())
+    }
+
+    /// Publish the bundle to `final_dir` so readers never see a partial tree.
+    ///
+    /// Files are extracted into a hidden sibling directory which is then
+    /// renamed into place; an existing `final_dir` is moved aside first and
+    /// removed once the swap succeeds, so stale files do not survive. The
+    /// temporary directory is removed on failure. Publishing to a `final_dir`
+    /// on a different filesystem than its parent (a mount point) is not
+    /// supported, since it cannot be renamed; the rename error is returned
+    /// and the existing tree is left untouched. Returns the number of files
+    /// written.
+    fn publish_to_directory(final_dir: &Path) -> Result<usize, SchemaError> {
+        let parent = match final_dir.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        std::fs::create_dir_all(parent).map_err(|e| SchemaError::CreateDirError {
+            path: parent.display().to_string(),
+            source: e,
+        })?;
+
+        let name = final_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "schemas".to_string());
+        let unique = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let temp = parent.join(format!(".{name}.tmp-{}-{unique}", std::process::id()));
+        let backup = parent.join(format!(".{name}.old-{}-{unique}", std::process::id()));
+
+        let count = match Self::write_to_directory(&temp) {
+            Ok(count) => count,
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&temp);
+                return Err(e);
+            }
+        };
+        let published = swap_directory(&temp, final_dir, &backup);
+        let _ = std::fs::remove_dir_all(&temp);
+        published.map_err(|e| SchemaError::WriteError {
+            path: final_dir.display().to_string(),
+            source: e,
+        })?;
+        Ok(count)
+    }
+
+    /// Write all files to `base` and mark each one read-only.
+    ///
+    /// On Unix this removes every write bit (`0o444` for default modes); on
+    /// Windows it sets the read-only attribute. Directories keep their default
+    /// permissions. Files left by an earlier extraction are deleted and
+    /// recreated rather than made writable again.
+    fn write_to_directory_readonly(base_path: &Path) -> Result<usize, SchemaError> {
+        let mut count = 0;
+
+        for file in Self::files() {
+            let full_path = base_path.join(file.path());
+            let write_error = |e| SchemaError::WriteError {
+                path: full_path.display().to_string(),
+                source: e,
+            };
+
+            if let Some(parent) = full_path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| SchemaError::CreateDirError {
+                    path: parent.display().to_string(),
+                    source: e,
+                })?;
+            }
+
+            match std::fs::remove_file(&full_path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(write_error(e)),
+                _ => {}
+            }
+            std::fs::write(&full_path, file.contents()).map_err(write_error)?;
+
+            let mut permissions = std::fs::metadata(&full_path)
+                .map_err(write_error)?
+                .permissions();
+            permissions.set_readonly(true);
+            std::fs::set_permissions(&full_path, permissions).map_err(write_error)?;
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Write all files to `base` through a `BufWriter` of `buf_size` bytes,
+    /// creating each directory only once.
+    ///
+    /// Produces the same tree as [`write_to_directory`](Self::write_to_directory).
+    /// Each file is still written with a single `write_all`, so the buffer
+    /// saves nothing per file; the saving is skipping `create_dir_all` for
+    /// directories already created, which helps bundles with many files per
+    /// directory.
+    fn write_to_directory_buffered(
+        base_path: &Path,
+        buf_size: usize,
+    ) -> Result<usize, SchemaError> {
+        use std::io::Write;
+
+        let mut created = std::collections::HashSet::new();
+        let mut count = 0;
+
+        for file in Self::files() {
+            let full_path = base_path.join(file.path());
+
+            if let Some(parent) = full_path.parent() {
+                if !created.contains(parent) {
+                    std::fs::create_dir_all(parent).map_err(|e| SchemaError::CreateDirError {
+                        path: parent.display().to_string(),
+                        source: e,
+                    })?;
+                    created.insert(parent.to_path_buf());
+                }
+            }
+
+            let write_error = |e| SchemaError::WriteError {
+                path: full_path.display().to_string(),
+                source: e,
+            };
+            let out = std::fs::File::create(&full_path).map_err(write_error)?;
+            let mut out = std::io::BufWriter::with_capacity(buf_size, out);
+            out.write_all(file.contents()).map_err(write_error)?;
+            out.flush().map_err(write_error)?;
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Write only the files that are missing from `base` or differ from the
+    /// embedded contents, leaving identical files untouched.
+    ///
+    /// Makes repeated extraction into a persistent cache near-instant when
+    /// nothing has changed.
+    fn write_to_directory_sync(base_path: &Path) -> Result<SyncReport, SchemaError> {
+        let mut report = SyncReport::default();
+
+        for file in Self::files() {
+            let full_path = base_path.join(file.path());
+            report.total += 1;
+
+            let unchanged = std::fs::metadata(&full_path)
+                .is_ok_and(|m| m.len() == file.contents().len() as u64)
+                && std::fs::read(&full_path).is_ok_and(|bytes| bytes == file.contents());
+            if unchanged {
+                report.skipped += 1;
+                continue;
+            }
+
+            if let Some(parent) = full_path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| SchemaError::CreateDirError {
+                    path: parent.display().to_string(),
+                    source: e,
+                })?;
+            }
+            std::fs::write(&full_path, file.contents()).map_err(|e| SchemaError::WriteError {
+                path: full_path.display().to_string(),
+                source: e,
+            })?;
+            report.written += 1;
+        }
+
+        Ok(report)
+    }
+
+    /// The largest file by content length, ties broken by path.
+    fn largest_file() -> Option<&'static File<'static>> {
+        Self::files_by_size_desc().into_iter().next()
+    }
+
+    /// All files ranked by content length, largest first, ties broken by path.
+    fn files_by_size_desc() -> Vec<&'static File<'static>> {
+        let mut files: Vec<_> = Self::files().collect();
+        files.sort_by(|a, b| {
+            b.contents()
+                .len()
+                .cmp(&a.contents().len())
+                .then_with(|| a.path().cmp(b.path()))
+        });
+        files
+    }
+
+    /// Total size in bytes of each directory, including all its subdirectories.
+    ///
+    /// Keys are `/`-separated directory paths; the bundle root is the empty
+    /// string and equals [`total_size`](Self::total_size).
+    fn directory_sizes() -> BTreeMap<String, usize> {
+        let mut sizes = BTreeMap::new();
+        for file in Self::files() {
+            let size = file.contents().len();
+            *sizes.entry(String::new()).or_default() += size;
+            let mut dir = String::new();
+            if let Some(parent) = file.path().parent() {
+                for component in parent.components() {
+                    if !dir.is_empty() {
+                        dir.push('/');
+                    }
+                    dir.push_str(&component.as_os_str().to_string_lossy());
+                    *sizes.entry(dir.clone()).or_default() += size;
+                }
+            }
+        }
+        sizes
+    }
+
+    /// Flatten the bundle into tree nodes for callers rendering their own
+    /// trees.
+    ///
+    /// Nodes are in depth-first order with the entries of each directory sorted
+    /// by name. Top-level entries have depth 0; the root itself is omitted.
+    fn tree_nodes() -> Vec<TreeNode> {
+        let mut nodes = Vec::new();
+        collect_tree_nodes(Self::dir(), 0, &mut nodes);
+        nodes
+    }
+
+    /// Each file's XML declaration (`<?xml ...?>`), or `None` if the file does
+    /// not start with one.
+    ///
+    /// A leading byte order mark is skipped. Handy for spotting inconsistent
+    /// prologs across a bundle.
+    fn xml_declarations() -> Vec<(&'static str, Option<String>)> {
+        Self::files()
+            .filter_map(|f| {
+                let declaration = f.contents_utf8().and_then(|text| {
+                    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+                    let rest = text.strip_prefix("<?xml")?;
+                    if !rest.starts_with(char::is_whitespace) {
+                        return None;
+                    }
+                    let end = text.find("?>")? + 2;
+                    Some(text[..end].trim().to_string())
+                });
+                Some((f.path().to_str()?, declaration))
+            })
+            .collect()
+    }
+
+    /// Files mentioning any of the given element or attribute names, with the
+    /// names each one mentions, sorted by path.
+    ///
+    /// A plain-text search for whole names: `topic` matches `"topic"` and
+    /// `dita:topic` but not `topicref`. Meant for auditing a bundle against a
+    /// caller-supplied list, such as deprecated names.
+    #[cfg(feature = "search")]
+    fn files_mentioning(names: &[&str]) -> Vec<(&'static str, Vec<String>)> {
+        let mut found: Vec<_> = Self::files()
+            .filter_map(|f| {
+                let path = f.path().to_str()?;
+                let text = f.contents_utf8()?;
+                let mut matched: Vec<String> = Vec::new();
+                for name in names {
+                    if search::mentions(text, name) && !matched.iter().any(|m| m == name) {
+                        matched.push(name.to_string());
+                    }
+                }
+                (!matched.is_empty()).then_some((path, matched))
+            })
+            .collect();
+        found.sort();
+        found
+    }
+
+    /// The most common file extension, ties broken alphabetically.
+    ///
+    /// A single label for the bundle's predominant format; `None` if no file
+    /// has an extension.
+    fn primary_extension() -> Option<&'static str> {
+        let mut counts: BTreeMap<&'static str, usize> = BTreeMap::new();
+        for ext in Self::files().filter_map(|f| f.path().extension()?.to_str()) {
+            *counts.entry(ext).or_default() += 1;
+        }
+        // max_by_key keeps the last maximum, so iterate in reverse to prefer
+        // the alphabetically first extension
+        counts
+            .into_iter()
+            .rev()
+            .max_by_key(|&(_, count)| count)
+            .map(|(ext, _)| ext)
+    }
+
+    /// Lines containing `pattern`, with their file and 1-based line number.
+    ///
+    /// The pattern is matched literally. Files are scanned lazily as the
+    /// iterator advances, so nothing is buffered; files that are not UTF-8
+    /// are skipped.
+    #[cfg(feature = "search")]
+    fn grep_lines(pattern: &str) -> impl Iterator<Item = GrepHit> {
+        let pattern = pattern.to_string();
+        Self::files().flat_map(move |f| {
+            let path = f.path().to_str().unwrap_or_default();
+            let pattern = pattern.clone();
+            f.contents_utf8()
+                .unwrap_or_default()
+                .lines()
+                .enumerate()
+                .filter(move |(_, line)| line.contains(&pattern))
+                .map(move |(i, line)| GrepHit {
+                    path,
+                    line_number: i + 1,
+                    line,
+                })
+        })
+    }
+
+    /// Iterate `(path, contents, content type)` for every file in one pass.
+    ///
+    /// Content types come from [`content_type`].
+    fn entries_with_content_type()
+    -> impl Iterator<Item = (&'static str, &'static [u8], &'static str)> {
+        Self::files().filter_map(|f| {
+            let path = f.path().to_str()?;
+            Some((path, f.contents(), content_type(path)))
+        })
+    }
+
+    /// Compare the embedded files against a directory snapshot.
+    ///
+    /// Paths are compared relative to `base`, so a fresh
+    /// [`write_to_directory`](Self::write_to_directory) yields an empty diff.
+    fn diff_against_directory(base: &Path) -> Result<BundleDiff, SchemaError> {
+        let mut on_disk = BTreeMap::new();
+        walk_directory(base, base, &mut on_disk)?;
+
+        let mut diff = BundleDiff::default();
+        for file in Self::files() {
+            let path = file.path().to_string_lossy().replace('\\', "/");
+            match on_disk.remove(&path) {
+                None => diff.removed.push(path),
+                Some(full_path) => {
+                    let bytes = std::fs::read(&full_path).map_err(|e| SchemaError::ReadError {
+                        path: full_path.display().to_string(),
+                        source: e,
+                    })?;
+                    if bytes != file.contents() {
+                        diff.changed.push(path);
+                    }
+                }
+            }
+        }
+        diff.added = on_disk.into_keys().collect();
+        diff.removed.sort();
+        diff.changed.sort();
+        Ok(diff)
+    }
+
     /// Calculate total size in bytes of all schema files.
     fn total_size() -> usize {
         Self::files().map(|f| f.contents().len()).sum()
     }
+
+    /// Encode all files into a single self-describing blob.
+    ///
+    /// Each record is a varint-prefixed UTF-8 path followed by varint-prefixed
+    /// contents; decode with [`unpack`].
+    fn pack() -> Vec<u8> {
+        pack::pack(Self::files())
+    }
+
+    /// Get a file's text, guaranteed to end with a newline.
+    ///
+    /// Borrows the embedded contents when they already end with `\n` and only
+    /// allocates when one has to be appended. Returns `None` if the file does
+    /// not exist or is not valid UTF-8.
+    fn content_str_ensure_newline(path: &str) -> Option<Cow<'static, str>> {
+        let text = Self::get_file(path)?.contents_utf8()?;
+        if text.ends_with('\n') {
+            Some(Cow::Borrowed(text))
+        } else {
+            Some(Cow::Owned(format!("{text}\n")))
+        }
+    }
+
+    /// Export all files as a JSON array of `{"path", "content_b64"}` records.
+    ///
+    /// Contents are standard padded base64 of the raw bytes and records are
+    /// sorted by path, so a browser client can rebuild the bundle from a
+    /// single text response.
+    #[cfg(feature = "json")]
+    fn to_base64_json() -> String {
+        let mut files: Vec<_> = Self::files().collect();
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        let records: Vec<String> = files
+            .iter()
+            .map(|f| {
+                format!(
+                    "{{\"path\":{},\"content_b64\":\"{}\"}}",
+                    json::string(&f.path().to_string_lossy()),
+                    json::base64(f.contents())
+                )
+            })
+            .collect();
+        format!("[{}]", records.join(","))
+    }
+
+    /// Export the directory tree as nested JSON objects for tree widgets.
+    ///
+    /// Directories are `{"name", "children": [...]}` and files are
+    /// `{"name", "size"}`, with each directory's entries sorted by name. The
+    /// root object is named after the bundle.
+    #[cfg(feature = "json")]
+    fn to_tree_json() -> String {
+        format!(
+            "{{\"name\":{},\"children\":{}}}",
+            json::string(Self::NAME),
+            tree_json_children(Self::dir())
+        )
+    }
+
+    /// SHA-256 digest of a file's contents.
+    #[cfg(feature = "checksums")]
+    fn checksum(path: &str) -> Option<[u8; 32]> {
+        Self::get_file(path).map(|f| checksum::sha256(f.contents()))
+    }
+
+    /// SHA-256 digest identifying the bundle's exact contents.
+    ///
+    /// Covers every file's path and contents in sorted path order, so it
+    /// changes when any file is added, removed, renamed or modified.
+    #[cfg(feature = "checksums")]
+    fn bundle_digest() -> [u8; 32] {
+        let mut files: Vec<_> = Self::files().collect();
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        let mut hasher = checksum::Sha256::default();
+        for file in files {
+            checksum::update_entry_header(
+                &mut hasher,
+                &file.path().to_string_lossy(),
+                file.contents().len(),
+            );
+            hasher.update(file.contents());
+        }
+        hasher.finish()
+    }
+
+    /// Write all files to `base` and compute [`bundle_digest`](Self::bundle_digest)
+    /// in the same pass.
+    ///
+    /// Files are written in sorted path order with their bytes fed to the hasher
+    /// as they are written. Returns the number of files written and the digest.
+    #[cfg(feature = "checksums")]
+    fn write_to_directory_hashed(base: &Path) -> Result<(usize, [u8; 32]), SchemaError> {
+        use std::io::Write;
+
+        let mut files: Vec<_> = Self::files().collect();
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+        let mut hasher = checksum::Sha256::default();
+        for file in &files {
+            let full_path = base.join(file.path());
+            if let Some(parent) = full_path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| SchemaError::CreateDirError {
+                    path: parent.display().to_string(),
+                    source: e,
+                })?;
+            }
+            let write_error = |e| SchemaError::WriteError {
+                path: full_path.display().to_string(),
+                source: e,
+            };
+            let mut out = std::fs::File::create(&full_path).map_err(write_error)?;
+            checksum::update_entry_header(
+                &mut hasher,
+                &file.path().to_string_lossy(),
+                file.contents().len(),
+            );
+            for chunk in file.contents().chunks(64 * 1024) {
+                hasher.update(chunk);
+                out.write_all(chunk).map_err(write_error)?;
+            }
+        }
+        Ok((files.len(), hasher.finish()))
+    }
+
+    /// Files whose SHA-256 digest differs from `baseline` or that it lacks,
+    /// sorted by path.
+    ///
+    /// `baseline` maps relative paths to digests, for example as recorded from
+    /// [`checksum`](Self::checksum) by an earlier release. Paths only present
+    /// in the baseline are ignored.
+    #[cfg(feature = "checksums")]
+    fn changed_files_since(baseline: &BTreeMap<String, [u8; 32]>) -> Vec<&'static File<'static>> {
+        let mut changed: Vec<_> = Self::files()
+            .filter(|f| {
+                baseline.get(f.path().to_string_lossy().as_ref())
+                    != Some(&checksum::sha256(f.contents()))
+            })
+            .collect();
+        changed.sort_by(|a, b| a.path().cmp(b.path()));
+        changed
+    }
+
+    /// Write a `SHA256SUMS` manifest covering every file into `base`.
+    ///
+    /// Returns the path of the written manifest.
+    #[cfg(feature = "checksums")]
+    fn write_checksum_manifest(base: &Path) -> Result<PathBuf, SchemaError> {
+        Self::write_checksum_manifest_filtered(base, |_| true)
+    }
+
+    /// Write a `SHA256SUMS` manifest covering only the files matching `predicate`.
+    ///
+    /// Lines use the `sha256sum` format (`<hex>  <path>`) and are sorted by path,
+    /// so the manifest can be checked with `sha256sum -c` from `base`.
+    #[cfg(feature = "checksums")]
+    fn write_checksum_manifest_filtered<F: Fn(&File) -> bool>(
+        base: &Path,
+        predicate: F,
+    ) -> Result<PathBuf, SchemaError> {
+        let mut entries: Vec<_> = Self::files()
+            .filter(|f| predicate(f))
+            .map(|f| (f.path().to_string_lossy(), checksum::sha256(f.contents())))
+            .collect();
+        entries.sort();
+
+        let manifest: String = entries
+            .iter()
+            .map(|(path, digest)| format!("{}  {path}\n", checksum::to_hex(digest)))
+            .collect();
+
+        std::fs::create_dir_all(base).map_err(|e| SchemaError::CreateDirError {
+            path: base.display().to_string(),
+            source: e,
+        })?;
+        let manifest_path = base.join(checksum::MANIFEST_NAME);
+        std::fs::write(&manifest_path, manifest).map_err(|e| SchemaError::WriteError {
+            path: manifest_path.display().to_string(),
+            source: e,
+        })?;
+        Ok(manifest_path)
+    }
+
+    /// Write all files to `base`, storing each distinct content once and
+    /// symlinking later identical files to the first copy.
+    ///
+    /// Links are relative, so the extracted tree can be moved as a whole.
+    /// Where symlinks cannot be created (unsupported platform, filesystem or
+    /// permissions), the duplicate is written as a regular copy instead.
+    /// Returns the number of paths materialized, links included.
+    #[cfg(feature = "checksums")]
+    fn write_to_directory_symlink_dups(base_path: &Path) -> Result<usize, SchemaError> {
+        let mut first_by_digest: std::collections::HashMap<[u8; 32], &Path> =
+            std::collections::HashMap::new();
+        let mut count = 0;
+
+        for file in Self::files() {
+            let full_path = base_path.join(file.path());
+            if let Some(parent) = full_path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| SchemaError::CreateDirError {
+                    path: parent.display().to_string(),
+                    source: e,
+                })?;
+            }
+
+            let digest = checksum::sha256(file.contents());
+            let linked = match first_by_digest.get(&digest) {
+                Some(original) => {
+                    let _ = std::fs::remove_file(&full_path);
+                    symlink_file(&relative_link(file.path(), original), &full_path).is_ok()
+                }
+                None => {
+                    first_by_digest.insert(digest, file.path());
+                    false
+                }
+            };
+            if !linked {
+                std::fs::write(&full_path, file.contents()).map_err(|e| {
+                    SchemaError::WriteError {
+                        path: full_path.display().to_string(),
+                        source: e,
+                    }
+                })?;
+            }
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Detect the version a file declares in its header comment or `xs:schema` `version` attribute.
+    #[cfg(feature = "xml")]
+    fn detected_version(path: &str) -> Option<String> {
+        xml::detect_version(Self::get_file(path)?.contents_utf8()?)
+    }
+
+    /// Check whether the versions found in the bundle's files agree with [`Self::VERSION`].
+    ///
+    /// Bundles vendor third-party modules (XLink, MathML, ...) that carry their own
+    /// versions, so the most commonly detected version is the one compared.
+    #[cfg(feature = "xml")]
+    fn version_matches_declared() -> bool {
+        let mut counts = BTreeMap::<String, usize>::new();
+        for file in Self::files() {
+            if let Some(version) = file.contents_utf8().and_then(xml::detect_version) {
+                *counts.entry(version).or_default() += 1;
+            }
+        }
+        let Some(max) = counts.values().copied().max() else {
+            return false;
+        };
+        counts
+            .iter()
+            .any(|(version, &n)| n == max && xml::version_matches(version, Self::VERSION))
+    }
+
+    /// List files whose detected [`SchemaLanguage`] disagrees with their extension,
+    /// e.g. an XSD saved as `.xml`.
+    ///
+    /// Files with unrecognised extensions or non-XML content are not checked.
+    #[cfg(feature = "xml")]
+    fn extension_language_mismatches() -> Vec<(&'static str, SchemaLanguage)> {
+        Self::files()
+            .filter_map(|f| {
+                let path = f.path().to_str()?;
+                let ext = f.path().extension()?.to_str()?;
+                let language = SchemaLanguage::detect(f.contents_utf8()?)?;
+                (language.matches_extension(ext) == Some(false)).then_some((path, language))
+            })
+            .collect()
+    }
+
+    /// Find XSD files whose `xs:schema` root declares the given `version` attribute.
+    ///
+    /// Files without a `version` attribute are never returned.
+    #[cfg(feature = "xml")]
+    fn files_by_schema_version(version: &str) -> Vec<&'static File<'static>> {
+        Self::files()
+            .filter(|f| {
+                f.contents_utf8()
+                    .filter(|text| SchemaLanguage::detect(text) == Some(SchemaLanguage::Xsd))
+                    .and_then(xml::schema_version)
+                    == Some(version)
+            })
+            .collect()
+    }
+
+    /// Count element, attribute and type declarations across all XSD and
+    /// RELAX NG files, as a rough measure of schema complexity.
+    #[cfg(feature = "xml")]
+    fn declaration_counts() -> DeclarationCounts {
+        let mut counts = DeclarationCounts::default();
+        for text in Self::files().filter_map(|f| f.contents_utf8()) {
+            if xml::is_schema(text) {
+                counts += DeclarationCounts::of(text);
+            }
+        }
+        counts
+    }
+
+    /// XSD and RELAX NG files ranked by a composite complexity score, highest
+    /// first, ties broken by path.
+    ///
+    /// Each file scores `size / max_size + lines / max_lines + decls / max_decls`,
+    /// where `decls` counts its element and type declarations and each maximum
+    /// is taken over the bundle's schema files. Scores range from 0.0 to 3.0 and
+    /// are only comparable within one bundle.
+    #[cfg(feature = "xml")]
+    fn complexity_ranking() -> Vec<(&'static str, f64)> {
+        let metrics: Vec<(&'static str, usize, usize, usize)> = Self::files()
+            .filter_map(|f| {
+                let text = f.contents_utf8().filter(|text| xml::is_schema(text))?;
+                let counts = DeclarationCounts::of(text);
+                Some((
+                    f.path().to_str()?,
+                    text.len(),
+                    text.lines().count(),
+                    counts.elements + counts.types,
+                ))
+            })
+            .collect();
+
+        let max = |metric: fn(&(&'static str, usize, usize, usize)) -> usize| {
+            metrics.iter().map(metric).max().unwrap_or(0).max(1) as f64
+        };
+        let (max_size, max_lines, max_decls) = (max(|m| m.1), max(|m| m.2), max(|m| m.3));
+
+        let mut ranking: Vec<(&'static str, f64)> = metrics
+            .iter()
+            .map(|&(path, size, lines, decls)| {
+                let score =
+                    size as f64 / max_size + lines as f64 / max_lines + decls as f64 / max_decls;
+                (path, score)
+            })
+            .collect();
+        ranking.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        ranking
+    }
+
+    /// The bundle's primary schema: the XSD or RELAX NG entry point (a file
+    /// no other file references) with the largest include/import closure.
+    ///
+    /// [`PRIMARY_SCHEMA`](Self::PRIMARY_SCHEMA) takes precedence when set,
+    /// e.g. for DocBook, whose four grammars are all single-file entry points.
+    #[cfg(feature = "xml")]
+    fn primary_file() -> Option<&'static File<'static>> {
+        if let Some(path) = Self::PRIMARY_SCHEMA {
+            return Self::get_file(path);
+        }
+        let graph = xml::Graph::new(Self::files());
+        graph.primary().map(|i| graph.files[i])
+    }
+
+    /// The target namespace of a schema file (`targetNamespace` for XSD, `ns`
+    /// for RELAX NG), or `None` for no-namespace schemas.
+    #[cfg(feature = "xml")]
+    fn target_namespace(path: &str) -> Option<&'static str> {
+        xml::target_namespace(Self::get_file(path)?.contents_utf8()?)
+    }
+
+    /// Distinct target namespaces declared by the bundle's XSD and RELAX NG
+    /// files, sorted.
+    #[cfg(feature = "xml")]
+    fn all_namespaces() -> Vec<&'static str> {
+        let mut namespaces: Vec<&'static str> = Self::files()
+            .filter_map(|f| f.contents_utf8())
+            .filter(|text| xml::is_schema(text))
+            .filter_map(xml::target_namespace)
+            .collect();
+        namespaces.sort_unstable();
+        namespaces.dedup();
+        namespaces
+    }
+
+    /// The given namespace URIs that no XSD or RELAX NG file in the bundle
+    /// targets, in input order.
+    ///
+    /// An empty URI stands for no namespace and is supported when some schema
+    /// declares no target namespace, as JATS does for its own vocabulary.
+    #[cfg(feature = "xml")]
+    fn unsupported_namespaces(uris: &[&str]) -> Vec<String> {
+        let namespaces = Self::all_namespaces();
+        let no_namespace = uris.contains(&"")
+            && Self::files()
+                .filter_map(|f| f.contents_utf8())
+                .any(|text| xml::is_schema(text) && xml::target_namespace(text).is_none());
+        uris.iter()
+            .filter(|uri| {
+                if uri.is_empty() {
+                    !no_namespace
+                } else {
+                    !namespaces.contains(uri)
+                }
+            })
+            .map(|uri| uri.to_string())
+            .collect()
+    }
+
+    /// Local name of a file's root element.
+    #[cfg(feature = "xml")]
+    fn root_element_name(path: &str) -> Option<&'static str> {
+        Some(xml::root_element(Self::get_file(path)?.contents_utf8()?)?.local_name())
+    }
+
+    /// Files with an `xs:import` of the given namespace.
+    ///
+    /// Useful to find which schemas pull in an external dependency such as
+    /// XLink or the XML namespace before mirroring it.
+    #[cfg(feature = "xml")]
+    fn files_importing_namespace(uri: &str) -> Vec<&'static File<'static>> {
+        Self::files()
+            .filter(|f| {
+                f.contents_utf8()
+                    .is_some_and(|text| xml::imported_namespaces(text).contains(&uri))
+            })
+            .collect()
+    }
+
+    /// Schema files declaring `name` as a global element.
+    ///
+    /// For XSD only top-level `xs:element` declarations count; RELAX NG has no
+    /// global scope, so any element pattern with that name does.
+    #[cfg(feature = "xml")]
+    fn files_with_global_element(name: &str) -> Vec<&'static File<'static>> {
+        Self::files()
+            .filter(|f| {
+                f.contents_utf8().is_some_and(|text| {
+                    xml::is_schema(text) && xml::declares_root_element(text, name)
+                })
+            })
+            .collect()
+    }
+
+    /// Split files into `(with_mathml, without_mathml)`.
+    ///
+    /// A file involves MathML when its path mentions MathML, or it imports or
+    /// defines the MathML namespace.
+    #[cfg(feature = "xml")]
+    fn partition_mathml() -> (Vec<&'static File<'static>>, Vec<&'static File<'static>>) {
+        Self::files().partition(|f| {
+            f.path()
+                .to_string_lossy()
+                .to_ascii_lowercase()
+                .contains("mathml")
+                || f.contents_utf8().is_some_and(|text| {
+                    xml::target_namespace(text) == Some(xml::MATHML_NS)
+                        || xml::imported_namespaces(text).contains(&xml::MATHML_NS)
+                })
+        })
+    }
+
+    /// MathML versions the bundle's MathML files provide, such as `"3.0"`.
+    ///
+    /// Versions are read from the paths and schema locations of the files
+    /// [`partition_mathml`](Self::partition_mathml) reports as involving
+    /// MathML, since both versions share the same namespace.
+    #[cfg(feature = "xml")]
+    fn mathml_versions() -> std::collections::BTreeSet<String> {
+        let (with_mathml, _) = Self::partition_mathml();
+        let mut versions = std::collections::BTreeSet::new();
+        for file in with_mathml {
+            versions.extend(xml::mathml_versions_in(&file.path().to_string_lossy()));
+            for location in xml::references(file.contents_utf8().unwrap_or_default()) {
+                versions.extend(xml::mathml_versions_in(location));
+            }
+        }
+        versions
+    }
+
+    /// Summarize the primary schema: its path, target namespace, imported
+    /// namespaces and root element.
+    #[cfg(feature = "xml")]
+    fn primary_schema_report() -> Option<PrimarySchemaReport> {
+        let file = Self::primary_file()?;
+        PrimarySchemaReport::of(file.path().to_str()?, file.contents_utf8()?)
+    }
+
+    /// Reference graph degrees of each XSD and RELAX NG file, as
+    /// `(path, fan_in, fan_out)`.
+    ///
+    /// `fan_in` counts the files including or importing a file and `fan_out`
+    /// the bundle files it references. URN and URL references are resolved
+    /// through the bundle's own catalogs; unresolvable ones are not counted.
+    #[cfg(feature = "xml")]
+    fn dependency_degrees() -> Vec<(&'static str, usize, usize)> {
+        let graph = xml::Graph::new(Self::files());
+        let fan_in = graph.fan_in();
+        graph
+            .files
+            .iter()
+            .zip(&graph.edges)
+            .zip(fan_in)
+            .enumerate()
+            .filter_map(|(i, ((file, targets), fan_in))| {
+                let fan_out = targets.iter().filter(|&&t| t != i).count();
+                Some((file.path().to_str()?, fan_in, fan_out))
+            })
+            .collect()
+    }
+
+    /// Include/import cycles among the XSD and RELAX NG files.
+    ///
+    /// Each cycle lists the files along it, starting from the smallest path;
+    /// the last file references the first. A file referencing itself forms a
+    /// one-element cycle. Every file on some cycle appears in at least one of
+    /// the reported ones.
+    #[cfg(feature = "xml")]
+    fn include_cycles() -> Vec<Vec<&'static str>> {
+        let graph = xml::Graph::new(Self::files());
+        graph
+            .cycles()
+            .into_iter()
+            .map(|cycle| {
+                cycle
+                    .into_iter()
+                    .filter_map(|i| graph.files[i].path().to_str())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Count the inline documentation blocks (`xs:documentation` or RELAX NG
+    /// `a:documentation`) in a file, as a coarse documentation-density metric.
+    #[cfg(feature = "xml")]
+    fn documentation_block_count(path: &str) -> Option<usize> {
+        Some(xml::documentation_blocks(
+            Self::get_file(path)?.contents_utf8()?,
+        ))
+    }
+
+    /// Total inline documentation blocks across all files.
+    #[cfg(feature = "xml")]
+    fn total_documentation_blocks() -> usize {
+        Self::files()
+            .filter_map(|f| f.contents_utf8())
+            .map(xml::documentation_blocks)
+            .sum()
+    }
+
+    /// Generate an OASIS XML catalog for the bundle's XSD and RELAX NG files.
+    ///
+    /// Paths in the catalog are relative to the bundle root, so it belongs next
+    /// to the extracted files.
+    #[cfg(feature = "xml")]
+    fn catalog_xml() -> String {
+        xml::catalog(&xml::Graph::new(Self::files()))
+    }
+
+    /// Write the generated catalog to `schemas-catalog.xml` in `base`.
+    ///
+    /// Returns the path of the written catalog.
+    #[cfg(feature = "xml")]
+    fn write_catalog(base: &Path) -> Result<PathBuf, SchemaError> {
+        std::fs::create_dir_all(base).map_err(|e| SchemaError::CreateDirError {
+            path: base.display().to_string(),
+            source: e,
+        })?;
+        let catalog_path = base.join(xml::CATALOG_NAME);
+        std::fs::write(&catalog_path, Self::catalog_xml()).map_err(|e| {
+            SchemaError::WriteError {
+                path: catalog_path.display().to_string(),
+                source: e,
+            }
+        })?;
+        Ok(catalog_path)
+    }
+
+    /// Write all files to `base` and generate a catalog alongside them.
+    ///
+    /// Returns the number of files written and the catalog path. Failures
+    /// report the path being written, so a catalog failure names the catalog
+    /// file. Already written files are not cleaned up on error.
+    #[cfg(feature = "xml")]
+    fn extract_with_catalog(base: &Path) -> Result<(usize, PathBuf), SchemaError> {
+        let count = Self::write_to_directory(base)?;
+        let catalog_path = Self::write_catalog(base)?;
+        Ok((count, catalog_path))
+    }
+
+    /// Write a zip archive of all files plus the generated catalog at its root,
+    /// ready to unzip and point a validator at.
+    ///
+    /// The catalog is named `schemas-catalog.xml`, like
+    /// [`write_catalog`](Self::write_catalog), rather than `catalog.xml`:
+    /// DITA 1.3, DITA LCE and DocBook already ship a `catalog.xml` at their
+    /// root, which a generated one would overwrite. Point validators at
+    /// `schemas-catalog.xml`. Entries are stored uncompressed in sorted path
+    /// order with fixed timestamps, making the archive reproducible. Returns
+    /// the number of bundle files written, not counting the catalog.
+    #[cfg(feature = "zip")]
+    fn write_validation_package_zip<W: std::io::Write + std::io::Seek>(
+        writer: W,
+    ) -> Result<usize, SchemaError> {
+        let mut files: Vec<_> = Self::files().collect();
+        files.sort_by(|a, b| a.path().cmp(b.path()));
+
+        let mut zip = zip::ZipWriter::new(writer).map_err(|e| SchemaError::WriteError {
+            path: "zip archive".to_string(),
+            source: e,
+        })?;
+        for file in &files {
+            let name = file.path().to_string_lossy();
+            zip.add(&name, file.contents())
+                .map_err(|e| SchemaError::WriteError {
+                    path: name.to_string(),
+                    source: e,
+                })?;
+        }
+        zip.add(xml::CATALOG_NAME, Self::catalog_xml().as_bytes())
+            .map_err(|e| SchemaError::WriteError {
+                path: xml::CATALOG_NAME.to_string(),
+                source: e,
+            })?;
+        zip.finish().map_err(|e| SchemaError::WriteError {
+            path: "zip archive".to_string(),
+            source: e,
+        })?;
+        Ok(files.len())
+    }
+
+    /// Compute the smallest set of files needed to validate a sample document.
+    ///
+    /// The document's root element and namespace select the entry-point schema
+    /// with the smallest include/import closure accepting that root: a global
+    /// element declaration for XSD, an element reachable from `start` for
+    /// RELAX NG. Schemas for any other namespaces declared on the root are
+    /// added if not already covered.
+    #[cfg(feature = "xml")]
+    fn minimal_schemas_for(document: &[u8]) -> Result<Vec<&'static File<'static>>, SchemaError> {
+        let text = std::str::from_utf8(document)
+            .map_err(|e| SchemaError::InvalidDocument(e.to_string()))?;
+        let root = xml::document_root(text)
+            .ok_or_else(|| SchemaError::InvalidDocument("no root element".to_string()))?;
+        let graph = xml::Graph::new(Self::files());
+        let closure = graph.minimal_closure(&root).ok_or_else(|| {
+            SchemaError::NoMatchingSchema(match root.namespace {
+                Some(ns) => format!("{{{ns}}}{}", root.local_name),
+                None => root.local_name.to_string(),
+            })
+        })?;
+        Ok(closure.into_iter().map(|i| graph.files[i]).collect())
+    }
+
+    /// Write a schema and everything it includes or imports into a single
+    /// flat directory.
+    ///
+    /// Every file in the closure of `entry` is written under its bare file
+    /// name, and references between them (relative or resolved through the
+    /// bundle's catalogs) are rewritten to those names. References leaving the
+    /// bundle are kept as they are. Fails with [`SchemaError::FlattenCollision`]
+    /// when two files in the closure share a name, and with
+    /// [`SchemaError::NoMatchingSchema`] when `entry` is not an XSD or RELAX
+    /// NG file. Returns the number of files written.
+    #[cfg(feature = "xml")]
+    fn write_flattened_shell(entry: &str, base: &Path) -> Result<usize, SchemaError> {
+        Self::get_file(entry).ok_or_else(|| SchemaError::FileNotFound(entry.to_string()))?;
+        let graph = xml::Graph::new(Self::files());
+        let start = graph
+            .files
+            .iter()
+            .position(|f| f.path().to_str() == Some(entry))
+            .ok_or_else(|| SchemaError::NoMatchingSchema(entry.to_string()))?;
+        let closure = graph.closure(start);
+
+        let name_of = |i: usize| -> String {
+            graph.files[i]
+                .path()
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        let mut names: BTreeMap<String, usize> = BTreeMap::new();
+        for &i in &closure {
+            if let Some(&other) = names.get(&name_of(i)) {
+                return Err(SchemaError::FlattenCollision {
+                    name: name_of(i),
+                    first: graph.files[other].path().to_string_lossy().into_owned(),
+                    second: graph.files[i].path().to_string_lossy().into_owned(),
+                });
+            }
+            names.insert(name_of(i), i);
+        }
+
+        std::fs::create_dir_all(base).map_err(|e| SchemaError::CreateDirError {
+            path: base.display().to_string(),
+            source: e,
+        })?;
+        for &i in &closure {
+            let text = graph.files[i].contents_utf8().unwrap_or_default();
+            let rewritten =
+                xml::rewrite_references(text, |location| graph.target(i, location).map(name_of));
+            let path = base.join(name_of(i));
+            std::fs::write(&path, rewritten).map_err(|e| SchemaError::WriteError {
+                path: path.display().to_string(),
+                source: e,
+            })?;
+        }
+        Ok(closure.len())
+    }
+
+    /// Check that every entry-point schema can be used as-is.
+    ///
+    /// Each entry point must be retrievable through `get_file`, and every file
+    /// in its include/import closure must be well-formed and reference only
+    /// files present in the bundle. References through URNs or URLs the
+    /// bundle's catalogs do not map are treated as external and not checked,
+    /// as are the paths listed in
+    /// [`EXTERNAL_REFERENCES`](Self::EXTERNAL_REFERENCES).
+    ///
+    /// Returns the paths of the failing entry points.
+    #[cfg(feature = "xml")]
+    fn verify_entry_points() -> Result<(), Vec<String>> {
+        let graph = xml::Graph::new(Self::files());
+        let failing: Vec<String> = graph
+            .entry_points()
+            .into_iter()
+            .filter_map(|entry| {
+                let path = graph.files[entry].path().to_str()?;
+                let ok = Self::get_file(path).is_some()
+                    && graph.closure(entry).into_iter().all(|i| {
+                        graph.missing[i]
+                            .iter()
+                            .all(|path| Self::EXTERNAL_REFERENCES.contains(&path.as_str()))
+                            && graph.files[i]
+                                .contents_utf8()
+                                .is_some_and(|text| xml::check_well_formed(text).is_ok())
+                    });
+                (!ok).then(|| path.to_string())
+            })
+            .collect();
+        if failing.is_empty() {
+            Ok(())
+        } else {
+            Err(failing)
+        }
+    }
 }
 
 /// Extension trait providing additional utilities.
@@ -140,6 +1443,61 @@ pub struct BundleSummary {
     pub total_size: usize,
 }
 
+/// Outcome of [`SchemaBundle::write_to_directory_sync`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Files that were missing or different and have been written.
+    pub written: usize,
+    /// Files already present with identical contents.
+    pub skipped: usize,
+    /// Total files in the bundle.
+    pub total: usize,
+}
+
+/// A file or directory in [`SchemaBundle::tree_nodes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeNode {
+    /// Nesting level, 0 for top-level entries.
+    pub depth: usize,
+    /// Whether the node is a directory.
+    pub is_dir: bool,
+    /// Final path component.
+    pub name: String,
+    /// Path relative to the bundle root.
+    pub path: String,
+}
+
+/// A matching line from [`SchemaBundle::grep_lines`].
+#[cfg(feature = "search")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrepHit {
+    /// Path of the file, relative to the bundle root.
+    pub path: &'static str,
+    /// 1-based line number.
+    pub line_number: usize,
+    /// The line, without its terminator.
+    pub line: &'static str,
+}
+
+/// Differences between a bundle and a directory, from
+/// [`SchemaBundle::diff_against_directory`]. All lists are sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BundleDiff {
+    /// Files present on disk but not in the bundle.
+    pub added: Vec<String>,
+    /// Files in the bundle but missing on disk.
+    pub removed: Vec<String>,
+    /// Files present in both with different contents.
+    pub changed: Vec<String>,
+}
+
+impl BundleDiff {
+    /// Whether the directory matches the bundle exactly.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
 impl std::fmt::Display for BundleSummary {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -150,6 +1508,161 @@ impl std::fmt::Display for BundleSummary {
     }
 }
 
+/// Relative paths present in both file sets with identical contents, sorted.
+///
+/// Useful for deduplicating a combined extraction of overlapping bundles,
+/// such as JATS and BITS which vendor the same MathML modules.
+pub fn shared_files(a: &[(&str, &[u8])], b: &[(&str, &[u8])]) -> Vec<String> {
+    let b: std::collections::HashMap<&str, &[u8]> = b.iter().copied().collect();
+    let mut shared: Vec<String> = a
+        .iter()
+        .filter(|(path, contents)| b.get(path) == Some(contents))
+        .map(|(path, _)| path.to_string())
+        .collect();
+    shared.sort();
+    shared.dedup();
+    shared
+}
+
+/// Relative paths in bundle `A` that `B` does not provide with identical
+/// contents, sorted.
+///
+/// The complement of [`shared_files`]: when extracting overlapping bundles
+/// into one tree, these are the files `A` adds on top of `B`.
+pub fn files_unique_to<A: SchemaBundle, B: SchemaBundle>() -> Vec<String> {
+    let mut unique: Vec<String> = A::files()
+        .filter(|f| {
+            B::get_file(&f.path().to_string_lossy()).map(File::contents) != Some(f.contents())
+        })
+        .map(|f| f.path().to_string_lossy().into_owned())
+        .collect();
+    unique.sort();
+    unique
+}
+
+/// Jaccard index of two bundles' relative path sets, from 0.0 (disjoint) to
+/// 1.0 (same paths).
+///
+/// Only paths are compared, not contents. Two empty bundles count as identical.
+pub fn file_set_similarity<A: SchemaBundle, B: SchemaBundle>() -> f64 {
+    let a: std::collections::HashSet<_> = A::list_paths().collect();
+    let b: std::collections::HashSet<_> = B::list_paths().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Whether a path names a license file.
+fn is_license_file(path: &Path) -> bool {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let ext = path.extension().and_then(|e| e.to_str());
+    ["license", "licence", "copying", "notice"]
+        .iter()
+        .any(|name| stem.eq_ignore_ascii_case(name))
+        && ext.is_none_or(|ext| ext.eq_ignore_ascii_case("txt") || ext.eq_ignore_ascii_case("md"))
+}
+
+/// Namespace of an instance document's root element, or `None` for
+/// no-namespace documents and input that is not UTF-8 XML.
+#[cfg(feature = "xml")]
+pub fn root_namespace(document: &[u8]) -> Option<&str> {
+    xml::document_root(std::str::from_utf8(document).ok()?)?.namespace
+}
+
+/// MIME content type for a schema file, based on its extension.
+pub fn content_type(path: &str) -> &'static str {
+    let ext = Path::new(path).extension().and_then(|e| e.to_str());
+    match ext {
+        Some("xsd" | "rng" | "sch" | "nvdl" | "xml" | "xsl") => "application/xml",
+        Some("dtd" | "mod" | "ent") => "application/xml-dtd",
+        Some("rnc") => "application/relax-ng-compact-syntax",
+        Some("txt") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Relative symlink target pointing from the file at `from` to the file at
+/// `to`, both relative to the same root.
+#[cfg(feature = "checksums")]
+fn relative_link(from: &Path, to: &Path) -> PathBuf {
+    let from_dir: Vec<_> = from
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from_dir.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut link = PathBuf::new();
+    for _ in common..from_dir.len() {
+        link.push("..");
+    }
+    link.extend(&to[common..]);
+    link
+}
+
+/// Create a symlink to a file, where the platform supports it.
+#[cfg(feature = "checksums")]
+fn symlink_file(target: &Path, link: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    return std::os::unix::fs::symlink(target, link);
+    #[cfg(windows)]
+    return std::os::windows::fs::symlink_file(target, link);
+    #[cfg(not(any(unix, windows)))]
+    return Err(std::io::ErrorKind::Unsupported.into());
+}
+
+/// Move the directory `from` to `to`, replacing any existing `to` by first
+/// renaming it to `backup`.
+fn swap_directory(from: &Path, to: &Path, backup: &Path) -> std::io::Result<()> {
+    let existed = match std::fs::rename(to, backup) {
+        Ok(()) => true,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => false,
+        Err(e) => return Err(e),
+    };
+    if let Err(e) = std::fs::rename(from, to) {
+        if existed {
+            // Put the previous tree back rather than leaving nothing
+            let _ = std::fs::rename(backup, to);
+        }
+        return Err(e);
+    }
+    if existed {
+        let _ = std::fs::remove_dir_all(backup);
+    }
+    Ok(())
+}
+
+/// Recursively collect files under `dir`, keyed by `/`-separated path relative to `root`.
+fn walk_directory(
+    root: &Path,
+    dir: &Path,
+    out: &mut BTreeMap<String, PathBuf>,
+) -> Result<(), SchemaError> {
+    let read_error = |e| SchemaError::ReadError {
+        path: dir.display().to_string(),
+        source: e,
+    };
+    for entry in std::fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        if path.is_dir() {
+            walk_directory(root, &path, out)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let key = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            out.insert(key, path);
+        }
+    }
+    Ok(())
+}
+
 /// Recursively count files in a directory.
 fn count_files(dir: &'static Dir<'static>) -> usize {
     let mut count = dir.files().count();
@@ -167,9 +1680,115 @@ fn collect_files(dir: &'static Dir<'static>, out: &mut Vec<&'static File<'static
     }
 }
 
+/// Recursively collect tree nodes, sorting each directory's entries by name.
+fn collect_tree_nodes(dir: &'static Dir<'static>, depth: usize, out: &mut Vec<TreeNode>) {
+    let mut entries: Vec<_> = dir.entries().iter().collect();
+    entries.sort_by(|a, b| a.path().file_name().cmp(&b.path().file_name()));
+    for entry in entries {
+        let path = entry.path();
+        out.push(TreeNode {
+            depth,
+            is_dir: entry.as_dir().is_some(),
+            name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            path: path.to_string_lossy().into_owned(),
+        });
+        if let Some(subdir) = entry.as_dir() {
+            collect_tree_nodes(subdir, depth + 1, out);
+        }
+    }
+}
+
+/// JSON array of a directory's entries for [`SchemaBundle::to_tree_json`].
+#[cfg(feature = "json")]
+fn tree_json_children(dir: &'static Dir<'static>) -> String {
+    let mut entries: Vec<_> = dir.entries().iter().collect();
+    entries.sort_by(|a, b| a.path().file_name().cmp(&b.path().file_name()));
+    let children: Vec<String> = entries
+        .into_iter()
+        .map(|entry| {
+            let name = entry
+                .path()
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            match entry {
+                DirEntry::Dir(subdir) => format!(
+                    "{{\"name\":{},\"children\":{}}}",
+                    json::string(&name),
+                    tree_json_children(subdir)
+                ),
+                DirEntry::File(file) => format!(
+                    "{{\"name\":{},\"size\":{}}}",
+                    json::string(&name),
+                    file.contents().len()
+                ),
+            }
+        })
+        .collect();
+    format!("[{}]", children.join(","))
+}
+
+/// Path of the embedded file under `dir` whose contents are exactly the
+/// slice `content`, compared by address and length.
+///
+/// Each bundle's address index is built on first use and kept for the life
+/// of the process, keyed by the address of its root directory.
+fn path_by_address(dir: &'static Dir<'static>, content: &[u8]) -> Option<&'static str> {
+    type Index = HashMap<(usize, usize), &'static str>;
+    static INDEXES: OnceLock<Mutex<HashMap<usize, Index>>> = OnceLock::new();
+
+    let mut indexes = INDEXES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let index = indexes
+        .entry(std::ptr::from_ref(dir) as usize)
+        .or_insert_with(|| {
+            let mut index = Index::new();
+            for file in all_files(dir) {
+                if let Some(path) = file.path().to_str() {
+                    let key = (file.contents().as_ptr() as usize, file.contents().len());
+                    index.entry(key).or_insert(path);
+                }
+            }
+            index
+        });
+    index
+        .get(&(content.as_ptr() as usize, content.len()))
+        .copied()
+}
+
 /// Get all files recursively as a Vec.
 fn all_files(dir: &'static Dir<'static>) -> Vec<&'static File<'static>> {
     let mut files = Vec::new();
     collect_files(dir, &mut files);
+    #[cfg(feature = "sorted-default")]
+    files.sort_by(|a, b| a.path().cmp(b.path()));
     files
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_license_file() {
+        for path in [
+            "LICENSE",
+            "LICENSE.txt",
+            "License.TXT",
+            "licence.md",
+            "COPYING",
+            "copying.txt",
+            "sub/NOTICE.md",
+        ] {
+            assert!(is_license_file(Path::new(path)), "{path}");
+        }
+        for path in ["license.xsd", "LICENSES", "my-license.txt", "README.md"] {
+            assert!(!is_license_file(Path::new(path)), "{path}");
+        }
+    }
+}
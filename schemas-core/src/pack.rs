@@ -0,0 +1,74 @@
+//! Framed single-blob encoding of a bundle's files.
+//!
+//! Each record is a LEB128 varint path length, the UTF-8 path, a varint
+//! content length and the raw content bytes. Records are concatenated with
+//! no header, so an empty blob is an empty bundle.
+
+use crate::{File, SchemaError};
+
+/// Encode files as concatenated `(path, contents)` records.
+pub(crate) fn pack<'a>(files: impl Iterator<Item = &'a File<'a>>) -> Vec<u8> {
+    let mut out = Vec::new();
+    for file in files {
+        let path = file.path().to_string_lossy();
+        write_varint(&mut out, path.len() as u64);
+        out.extend_from_slice(path.as_bytes());
+        write_varint(&mut out, file.contents().len() as u64);
+        out.extend_from_slice(file.contents());
+    }
+    out
+}
+
+/// Decode a blob produced by [`SchemaBundle::pack`](crate::SchemaBundle::pack)
+/// back into `(path, contents)` pairs.
+pub fn unpack(mut data: &[u8]) -> Result<Vec<(String, Vec<u8>)>, SchemaError> {
+    let mut records = Vec::new();
+    while !data.is_empty() {
+        let path = read_chunk(&mut data)?;
+        let path = String::from_utf8(path.to_vec())
+            .map_err(|e| SchemaError::MalformedPack(format!("path is not UTF-8: {e}")))?;
+        let contents = read_chunk(&mut data)?;
+        records.push((path, contents.to_vec()));
+    }
+    Ok(records)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(data: &mut &[u8]) -> Result<u64, SchemaError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = data
+            .split_first()
+            .ok_or_else(|| SchemaError::MalformedPack("truncated length".to_string()))?;
+        *data = rest;
+        // The tenth byte only has room for the top bit of a u64
+        if shift == 63 && byte > 1 {
+            break;
+        }
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(SchemaError::MalformedPack(
+        "length overflows u64".to_string(),
+    ))
+}
+
+/// Read a varint-prefixed chunk, advancing `data` past it.
+fn read_chunk<'a>(data: &mut &'a [u8]) -> Result<&'a [u8], SchemaError> {
+    let len = usize::try_from(read_varint(data)?)
+        .ok()
+        .filter(|&len| len <= data.len())
+        .ok_or_else(|| SchemaError::MalformedPack("record exceeds input".to_string()))?;
+    let (chunk, rest) = data.split_at(len);
+    *data = rest;
+    Ok(chunk)
+}
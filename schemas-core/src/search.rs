@@ -0,0 +1,19 @@
+//! Plain-text search backing the `search` feature.
+
+/// Whether `c` can be part of an unprefixed XML name. The namespace prefix
+/// separator is excluded so `xs:element` mentions `element`.
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+/// Whether `name` occurs in `text` as a whole name, not as part of a longer one.
+pub(crate) fn mentions(text: &str, name: &str) -> bool {
+    if name.is_empty() {
+        return false;
+    }
+    text.match_indices(name).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + name.len()..].chars().next();
+        !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char)
+    })
+}
@@ -0,0 +1,60 @@
+//! Minimal ustar writer backing the `tar` feature.
+//!
+//! Every entry gets the same metadata (mtime 0, uid/gid 0, mode 0644, no user
+//! or group names), so identical input always yields byte-identical output.
+
+use std::io::{self, Write};
+
+const BLOCK: usize = 512;
+
+/// Write `value` as a NUL-terminated, zero-padded octal field.
+fn octal(field: &mut [u8], value: u64) {
+    let digits = field.len() - 1;
+    let text = format!("{value:0digits$o}");
+    field[..digits].copy_from_slice(text.as_bytes());
+    field[digits] = 0;
+}
+
+/// Split a path into ustar `(prefix, name)` fields, or `None` if it fits neither.
+fn split_path(path: &str) -> Option<(&str, &str)> {
+    if path.len() <= 100 {
+        return Some(("", path));
+    }
+    path.match_indices('/')
+        .map(|(i, _)| (&path[..i], &path[i + 1..]))
+        .find(|(prefix, name)| prefix.len() <= 155 && name.len() <= 100 && !name.is_empty())
+}
+
+/// Append one regular file entry.
+pub(crate) fn append_file(out: &mut impl Write, path: &str, data: &[u8]) -> io::Result<()> {
+    let (prefix, name) = split_path(path)
+        .ok_or_else(|| io::Error::other(format!("path too long for ustar: {path}")))?;
+
+    let mut header = [0u8; BLOCK];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    octal(&mut header[100..108], 0o644);
+    octal(&mut header[108..116], 0); // uid
+    octal(&mut header[116..124], 0); // gid
+    octal(&mut header[124..136], data.len() as u64);
+    octal(&mut header[136..148], 0); // mtime
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+
+    // The checksum is computed with its own field set to spaces
+    header[148..156].fill(b' ');
+    let sum: u64 = header.iter().map(|&b| u64::from(b)).sum();
+    octal(&mut header[148..155], sum);
+
+    out.write_all(&header)?;
+    out.write_all(data)?;
+    let padding = (BLOCK - data.len() % BLOCK) % BLOCK;
+    out.write_all(&[0; BLOCK][..padding])
+}
+
+/// Write the two zero blocks ending an archive.
+pub(crate) fn finish(out: &mut impl Write) -> io::Result<()> {
+    out.write_all(&[0; 2 * BLOCK])?;
+    out.flush()
+}
@@ -0,0 +1,951 @@
+//! Lightweight XML scanning helpers backing the `xml` feature.
+//!
+//! Schema files are well-formed and machine-generated, so a small tag
+//! scanner is enough to pull out root elements, attributes and header
+//! metadata without pulling in a full XML parser.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use crate::File;
+
+/// File name of the catalog written by `write_catalog`, chosen so it does not
+/// clash with the `catalog.xml` files some bundles ship.
+pub(crate) const CATALOG_NAME: &str = "schemas-catalog.xml";
+
+/// The kind of markup tag produced by [`tags`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TagKind {
+    Start,
+    End,
+    Empty,
+}
+
+/// A single start, end or empty-element tag.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Tag<'a> {
+    pub kind: TagKind,
+    /// Qualified name, e.g. `xs:element`.
+    pub name: &'a str,
+    /// Raw attribute text following the name.
+    pub attrs: &'a str,
+    /// Nesting depth; the root element is at depth 0.
+    pub depth: usize,
+}
+
+impl<'a> Tag<'a> {
+    /// Local part of the tag name (without prefix).
+    pub fn local_name(&self) -> &'a str {
+        local_name(self.name)
+    }
+
+    /// Value of the attribute with the given qualified name.
+    pub fn attr(&self, name: &str) -> Option<&'a str> {
+        attr(self.attrs, name)
+    }
+}
+
+/// Iterator over the tags of a document, skipping comments, processing
+/// instructions, doctype declarations and CDATA sections.
+pub(crate) struct Tags<'a> {
+    text: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+/// Scan the tags of `text` in document order.
+pub(crate) fn tags(text: &str) -> Tags<'_> {
+    Tags {
+        text,
+        pos: 0,
+        depth: 0,
+    }
+}
+
+impl<'a> Iterator for Tags<'a> {
+    type Item = Tag<'a>;
+
+    fn next(&mut self) -> Option<Tag<'a>> {
+        loop {
+            let rest = &self.text[self.pos..];
+            let start = rest.find('<')?;
+            let rest = &rest[start..];
+
+            if rest.starts_with("<!") || rest.starts_with("<?") {
+                let skip_to =
+                    |end: &str, from: usize| rest[from..].find(end).map(|i| from + i + end.len());
+                let consumed = if rest.starts_with("<!--") {
+                    skip_to("-->", 4)
+                } else if rest.starts_with("<![CDATA[") {
+                    skip_to("]]>", 9)
+                } else if rest.starts_with("<?") {
+                    skip_to("?>", 2)
+                } else {
+                    skip_declaration(rest)
+                };
+                self.pos += start + consumed.unwrap_or(rest.len());
+                continue;
+            }
+
+            let end = tag_end(rest)?;
+            let inner = &rest[1..end];
+            self.pos += start + end + 1;
+
+            if let Some(name) = inner.strip_prefix('/') {
+                self.depth = self.depth.saturating_sub(1);
+                return Some(Tag {
+                    kind: TagKind::End,
+                    name: name.trim(),
+                    attrs: "",
+                    depth: self.depth,
+                });
+            }
+
+            let (inner, kind) = match inner.strip_suffix('/') {
+                Some(inner) => (inner, TagKind::Empty),
+                None => (inner, TagKind::Start),
+            };
+            let name_end = inner
+                .find(|c: char| c.is_whitespace())
+                .unwrap_or(inner.len());
+            let tag = Tag {
+                kind,
+                name: &inner[..name_end],
+                attrs: &inner[name_end..],
+                depth: self.depth,
+            };
+            if kind == TagKind::Start {
+                self.depth += 1;
+            }
+            return Some(tag);
+        }
+    }
+}
+
+/// Find the `>` closing a tag, ignoring any inside quoted attribute values.
+fn tag_end(rest: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in rest.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Length of a `<!DOCTYPE ...>` style declaration, including any internal subset.
+fn skip_declaration(rest: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in rest.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            '>' if depth == 0 => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Strip the namespace prefix from a qualified name.
+pub(crate) fn local_name(name: &str) -> &str {
+    name.rsplit_once(':').map_or(name, |(_, local)| local)
+}
+
+/// Look up an attribute value by qualified name in raw attribute text.
+pub(crate) fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    attributes(attrs).find(|(n, _)| *n == name).map(|(_, v)| v)
+}
+
+/// Iterate `(name, value)` pairs in raw attribute text.
+pub(crate) fn attributes(attrs: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut rest = attrs;
+    std::iter::from_fn(move || {
+        let eq = rest.find('=')?;
+        let name = rest[..eq].trim();
+        let after = rest[eq + 1..].trim_start();
+        let quote = after.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value_len = after[1..].find(quote)?;
+        let value = &after[1..1 + value_len];
+        rest = &after[value_len + 2..];
+        Some((name, value))
+    })
+}
+
+/// Check that a document is well-formed enough to parse: a single root
+/// element, properly nested and closed tags, and no truncated markup.
+pub(crate) fn check_well_formed(text: &str) -> Result<(), String> {
+    let mut open: Vec<&str> = Vec::new();
+    let mut roots = 0;
+    let mut iter = tags(text);
+    for tag in iter.by_ref() {
+        match tag.kind {
+            TagKind::Start | TagKind::Empty if open.is_empty() => {
+                roots += 1;
+                if roots > 1 {
+                    return Err(format!("second root element <{}>", tag.name));
+                }
+                if tag.kind == TagKind::Start {
+                    open.push(tag.name);
+                }
+            }
+            TagKind::Start => open.push(tag.name),
+            TagKind::Empty => {}
+            TagKind::End => match open.pop() {
+                Some(name) if name == tag.name => {}
+                Some(name) => return Err(format!("</{}> closes <{name}>", tag.name)),
+                None => return Err(format!("unexpected </{}>", tag.name)),
+            },
+        }
+    }
+    if iter.text[iter.pos..].contains('<') {
+        return Err("unterminated markup".to_string());
+    }
+    match (roots, open.last()) {
+        (0, _) => Err("no root element".to_string()),
+        (_, Some(name)) => Err(format!("<{name}> is never closed")),
+        _ => Ok(()),
+    }
+}
+
+/// The root element of a document, if any.
+pub(crate) fn root_element(text: &str) -> Option<Tag<'_>> {
+    tags(text).find(|t| t.kind != TagKind::End)
+}
+
+/// Extract a version string from a file's header or its `xs:schema` `version` attribute.
+///
+/// Header comments and annotations of the form `VERSION: 1.4` take priority,
+/// since the `version` attribute often tracks an unrelated module revision.
+pub(crate) fn detect_version(text: &str) -> Option<String> {
+    header_version(text).or_else(|| schema_version(text).map(str::to_string))
+}
+
+/// The `version` attribute of an `xs:schema` root element.
+pub(crate) fn schema_version(text: &str) -> Option<&str> {
+    root_element(text)
+        .filter(|root| root.local_name() == "schema")
+        .and_then(|root| root.attr("version"))
+}
+
+/// Number of leading bytes searched for a header version marker.
+const HEADER_SCAN_LEN: usize = 8 * 1024;
+
+fn header_version(text: &str) -> Option<String> {
+    let mut end = text.len().min(HEADER_SCAN_LEN);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let header = &text[..end];
+    let lower = header.to_ascii_lowercase();
+
+    let mut from = 0;
+    while let Some(i) = lower[from..].find("version") {
+        let after = &header[from + i + "version".len()..];
+        from += i + "version".len();
+
+        // `version="1.0"` is an attribute, not a header marker
+        let after = after.strip_prefix(':').unwrap_or(after);
+        let trimmed = after.trim_start();
+        if trimmed.len() == after.len() && !after.starts_with(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+        let version: String = trimmed
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        let version = version.trim_end_matches('.');
+        if !version.is_empty() && version.starts_with(|c: char| c.is_ascii_digit()) {
+            return Some(version.to_string());
+        }
+    }
+    None
+}
+
+/// Whether a detected version is consistent with a declared one.
+///
+/// `1.4` and `1.4.1` are both consistent with a declared `1.4`.
+pub(crate) fn version_matches(detected: &str, declared: &str) -> bool {
+    detected == declared
+        || detected
+            .strip_prefix(declared)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// The language of a file in a schema bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SchemaLanguage {
+    /// W3C XML Schema (`.xsd`).
+    Xsd,
+    /// RELAX NG, XML syntax (`.rng`).
+    RelaxNg,
+    /// ISO Schematron (`.sch`).
+    Schematron,
+    /// Namespace-based Validation Dispatching Language (`.nvdl`).
+    Nvdl,
+    /// OASIS XML catalog.
+    Catalog,
+    /// Any other XML document.
+    Xml,
+}
+
+impl SchemaLanguage {
+    /// Detect the language of a document from its root element and namespace.
+    ///
+    /// Returns `None` for content that is not XML (e.g. RELAX NG compact syntax).
+    pub fn detect(text: &str) -> Option<Self> {
+        let root = document_root(text)?;
+        Some(match (root.namespace, root.local_name) {
+            (Some("http://www.w3.org/2001/XMLSchema"), "schema") => Self::Xsd,
+            (Some("http://relaxng.org/ns/structure/1.0"), "grammar" | "element") => Self::RelaxNg,
+            (
+                Some("http://purl.oclc.org/dsdl/schematron" | "http://www.ascc.net/xml/schematron"),
+                "schema",
+            ) => Self::Schematron,
+            (Some("http://purl.oclc.org/dsdl/nvdl/ns/structure/1.0"), "rules") => Self::Nvdl,
+            (Some("urn:oasis:names:tc:entity:xmlns:xml:catalog"), "catalog") => Self::Catalog,
+            _ => Self::Xml,
+        })
+    }
+
+    /// Whether a file extension is appropriate for this language.
+    ///
+    /// Returns `None` for extensions that imply no particular XML language.
+    pub fn matches_extension(self, ext: &str) -> Option<bool> {
+        let expected: &[Self] = match ext {
+            "xsd" => &[Self::Xsd],
+            "rng" => &[Self::RelaxNg],
+            "sch" => &[Self::Schematron],
+            "nvdl" => &[Self::Nvdl],
+            "xml" => &[Self::Catalog, Self::Xml],
+            _ => return None,
+        };
+        Some(expected.contains(&self))
+    }
+}
+
+/// Aggregate declaration counts across a bundle's schema files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeclarationCounts {
+    /// Named element declarations.
+    pub elements: usize,
+    /// Named attribute declarations.
+    pub attributes: usize,
+    /// Named type definitions (XSD `complexType`/`simpleType`, RELAX NG `define`).
+    pub types: usize,
+}
+
+impl DeclarationCounts {
+    /// Count the declarations in a single XSD or RELAX NG document.
+    pub(crate) fn of(text: &str) -> Self {
+        let mut counts = Self::default();
+        for tag in tags(text) {
+            if tag.kind == TagKind::End || tag.attr("name").is_none() {
+                continue;
+            }
+            match tag.local_name() {
+                "element" => counts.elements += 1,
+                "attribute" => counts.attributes += 1,
+                "complexType" | "simpleType" | "define" => counts.types += 1,
+                _ => {}
+            }
+        }
+        counts
+    }
+}
+
+impl std::ops::AddAssign for DeclarationCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.elements += other.elements;
+        self.attributes += other.attributes;
+        self.types += other.types;
+    }
+}
+
+/// Number of `documentation` elements (XSD `xs:documentation`, RELAX NG
+/// `a:documentation`) in a document.
+pub(crate) fn documentation_blocks(text: &str) -> usize {
+    tags(text)
+        .filter(|t| t.kind != TagKind::End && t.local_name() == "documentation")
+        .count()
+}
+
+/// The namespace a schema document defines: `targetNamespace` for XSD, `ns` for RELAX NG.
+pub(crate) fn target_namespace(text: &str) -> Option<&str> {
+    let root = root_element(text)?;
+    match root.local_name() {
+        "schema" => root.attr("targetNamespace"),
+        "grammar" => root.attr("ns"),
+        _ => None,
+    }
+    .filter(|ns| !ns.is_empty())
+}
+
+/// Whether a document is an XSD or RELAX NG schema.
+pub(crate) fn is_schema(text: &str) -> bool {
+    matches!(
+        SchemaLanguage::detect(text),
+        Some(SchemaLanguage::Xsd | SchemaLanguage::RelaxNg)
+    )
+}
+
+/// Whether a schema document declares an element with the given local name.
+///
+/// For XSD only top-level (global) declarations count, since only those can
+/// be document roots. RELAX NG has no such scoping, so any named `element`
+/// pattern counts.
+pub(crate) fn declares_root_element(text: &str, name: &str) -> bool {
+    let Some(root) = root_element(text) else {
+        return false;
+    };
+    let xsd = root.local_name() == "schema";
+    tags(text).any(|t| {
+        t.kind != TagKind::End
+            && t.local_name() == "element"
+            && (!xsd || t.depth == 1)
+            && t.attr("name").is_some_and(|n| local_name(n) == name)
+    })
+}
+
+/// Local names of the elements a RELAX NG grammar accepts as document root.
+///
+/// `texts` are the files making up the grammar, i.e. an entry point and
+/// everything it includes. Starting from each `start` pattern, `ref`s are
+/// followed into their `define`s and every named `element` pattern reached
+/// is collected, without descending into element content.
+pub(crate) fn start_elements<'a>(texts: impl Iterator<Item = &'a str>) -> BTreeSet<&'a str> {
+    let files: Vec<Vec<Tag<'a>>> = texts.map(|text| tags(text).collect()).collect();
+    let mut starts = Vec::new();
+    let mut defines: HashMap<&str, Vec<&[Tag<'a>]>> = HashMap::new();
+    for tags in &files {
+        let mut i = 0;
+        while i < tags.len() {
+            let tag = tags[i];
+            if tag.kind == TagKind::Start && matches!(tag.local_name(), "start" | "define") {
+                let end = matching_end(tags, i);
+                let body = &tags[i + 1..end];
+                match tag.attr("name") {
+                    Some(name) if tag.local_name() == "define" => {
+                        defines.entry(name).or_default().push(body);
+                    }
+                    _ => starts.push(body),
+                }
+                i = end;
+            }
+            i += 1;
+        }
+    }
+
+    let mut roots = BTreeSet::new();
+    let mut followed = HashSet::new();
+    while let Some(body) = starts.pop() {
+        let mut i = 0;
+        while i < body.len() {
+            let tag = body[i];
+            match (tag.kind, tag.local_name()) {
+                (TagKind::End, _) => {}
+                (_, "element") => {
+                    roots.extend(tag.attr("name").map(local_name));
+                    if tag.kind == TagKind::Start {
+                        i = matching_end(body, i);
+                    }
+                }
+                (_, "ref" | "parentRef") => {
+                    if let Some(name) = tag.attr("name").filter(|&n| followed.insert(n)) {
+                        starts.extend(defines.get(name).into_iter().flatten());
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+    roots
+}
+
+/// Index of the end tag closing the start tag at `start`, or `tags.len()`.
+fn matching_end(tags: &[Tag<'_>], start: usize) -> usize {
+    let depth = tags[start].depth;
+    tags[start + 1..]
+        .iter()
+        .position(|t| t.kind == TagKind::End && t.depth == depth)
+        .map_or(tags.len(), |p| start + 1 + p)
+}
+
+/// The MathML namespace.
+pub(crate) const MATHML_NS: &str = "http://www.w3.org/1998/Math/MathML";
+
+/// MathML version labels (`"2.0"`, `"3.0"`) named in a path or schema
+/// location, as in `mathml3.xsd` or `MathML-2/`.
+///
+/// MathML 2 and 3 share one namespace, so file names are the only reliable
+/// version marker in schema sets.
+pub(crate) fn mathml_versions_in(text: &str) -> Vec<String> {
+    let lower = text.to_ascii_lowercase();
+    lower
+        .match_indices("mathml")
+        .filter_map(|(i, m)| {
+            let rest = &lower[i + m.len()..];
+            let rest = rest.strip_prefix(['-', '_', ' ']).unwrap_or(rest);
+            let major = rest.chars().next()?.to_digit(10)?;
+            Some(format!("{major}.0"))
+        })
+        .collect()
+}
+
+/// Namespaces a schema imports, in document order without duplicates.
+pub(crate) fn imported_namespaces(text: &str) -> Vec<&str> {
+    let mut namespaces = Vec::new();
+    for tag in tags(text) {
+        if tag.kind != TagKind::End && tag.local_name() == "import" {
+            if let Some(ns) = tag.attr("namespace").filter(|ns| !namespaces.contains(ns)) {
+                namespaces.push(ns);
+            }
+        }
+    }
+    namespaces
+}
+
+/// Summary of a bundle's primary schema, from `primary_schema_report`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimarySchemaReport {
+    /// Relative path of the primary schema.
+    pub path: &'static str,
+    /// Its target namespace; `None` for no-namespace schemas such as JATS.
+    pub target_namespace: Option<&'static str>,
+    /// Namespaces it imports.
+    pub imported_namespaces: Vec<&'static str>,
+    /// Local name of its root element (`schema` for XSD, `grammar` for RELAX NG).
+    pub root_element: &'static str,
+}
+
+impl PrimarySchemaReport {
+    pub(crate) fn of(path: &'static str, text: &'static str) -> Option<Self> {
+        Some(Self {
+            path,
+            target_namespace: target_namespace(text),
+            imported_namespaces: imported_namespaces(text),
+            root_element: root_element(text)?.local_name(),
+        })
+    }
+}
+
+/// Locations of the schema documents referenced via include/import/redefine/override
+/// (XSD) or include/externalRef (RELAX NG).
+pub(crate) fn references(text: &str) -> impl Iterator<Item = &str> {
+    tags(text)
+        .filter(|t| t.kind != TagKind::End)
+        .filter_map(|t| match t.local_name() {
+            "include" | "import" | "redefine" | "override" => {
+                t.attr("schemaLocation").or_else(|| t.attr("href"))
+            }
+            "externalRef" => t.attr("href"),
+            _ => None,
+        })
+}
+
+/// Rewrite the include/import locations of a schema, replacing each one for
+/// which `replace` returns a new value and keeping the rest verbatim.
+pub(crate) fn rewrite_references(text: &str, replace: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    for location in references(text) {
+        let Some(replacement) = replace(location) else {
+            continue;
+        };
+        // Locations are slices of `text`, so their offsets locate them
+        let start = location.as_ptr() as usize - text.as_ptr() as usize;
+        out.push_str(&text[copied..start]);
+        out.push_str(&escape_attr(&replacement));
+        copied = start + location.len();
+    }
+    out.push_str(&text[copied..]);
+    out
+}
+
+/// Resolve a reference location against the path of the referencing file.
+///
+/// Returns `None` for absolute URLs and URNs, or for relative paths escaping
+/// the bundle root.
+pub(crate) fn resolve(base: &str, location: &str) -> Option<String> {
+    if location.contains(':') || location.starts_with('/') {
+        return None;
+    }
+    let mut parts: Vec<&str> = match base.rsplit_once('/') {
+        Some((dir, _)) => dir.split('/').collect(),
+        None => Vec::new(),
+    };
+    for segment in location.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            segment => parts.push(segment),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// The root element of an instance document, with its namespace resolved.
+pub(crate) struct DocumentRoot<'a> {
+    pub local_name: &'a str,
+    pub namespace: Option<&'a str>,
+    /// All namespace URIs declared on the root element.
+    pub declared: Vec<&'a str>,
+}
+
+/// Read the root element and namespace declarations of an instance document.
+pub(crate) fn document_root(text: &str) -> Option<DocumentRoot<'_>> {
+    let root = root_element(text)?;
+    let ns_attr = match root.name.split_once(':') {
+        Some((prefix, _)) => format!("xmlns:{prefix}"),
+        None => "xmlns".to_string(),
+    };
+    Some(DocumentRoot {
+        local_name: root.local_name(),
+        namespace: root.attr(&ns_attr).filter(|ns| !ns.is_empty()),
+        declared: attributes(root.attrs)
+            .filter(|(name, value)| {
+                (*name == "xmlns" || name.starts_with("xmlns:")) && !value.is_empty()
+            })
+            .map(|(_, value)| value)
+            .collect(),
+    })
+}
+
+/// Escape text for use in a double-quoted attribute value.
+pub(crate) fn escape_attr(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// The `system` and `uri` entries of an OASIS catalog, as `(identifier,
+/// bundle-relative path)` pairs. `xml:base` on entries and their ancestors
+/// is honoured.
+pub(crate) fn catalog_entries<'a>(catalog_path: &str, text: &'a str) -> Vec<(&'a str, String)> {
+    let mut bases: Vec<(usize, &str)> = Vec::new();
+    let mut entries = Vec::new();
+    for tag in tags(text) {
+        if tag.kind == TagKind::End {
+            if bases.last().is_some_and(|(depth, _)| *depth == tag.depth) {
+                bases.pop();
+            }
+            continue;
+        }
+        let own_base = tag.attr("xml:base");
+        let id = match tag.local_name() {
+            "system" => tag.attr("systemId"),
+            "uri" => tag.attr("name"),
+            _ => None,
+        };
+        if let (Some(id), Some(uri)) = (id, tag.attr("uri")) {
+            let mut location: String = bases.iter().map(|(_, base)| *base).collect();
+            location.push_str(own_base.unwrap_or_default());
+            location.push_str(uri);
+            if let Some(target) = resolve(catalog_path, &location) {
+                entries.push((id, target));
+            }
+        } else if let (TagKind::Start, Some(base)) = (tag.kind, own_base) {
+            bases.push((tag.depth, base));
+        }
+    }
+    entries
+}
+
+/// Intra-bundle reference graph over the schema files of a bundle.
+pub(crate) struct Graph {
+    pub files: Vec<&'static File<'static>>,
+    /// Indices of the files each file references.
+    pub edges: Vec<Vec<usize>>,
+    /// Relative references of each file that resolve to no bundle file.
+    pub missing: Vec<Vec<String>>,
+    /// Catalog `system`/`uri` mappings to bundle paths.
+    catalog: HashMap<&'static str, String>,
+    /// Index of each file by path.
+    index: HashMap<&'static str, usize>,
+}
+
+impl Graph {
+    /// Build the graph from all XSD and RELAX NG files.
+    ///
+    /// References that are not relative paths (URNs, URLs) are resolved
+    /// through the `system`/`uri` entries of any catalogs in the bundle.
+    pub fn new(files: impl Iterator<Item = &'static File<'static>>) -> Self {
+        let (files, others): (Vec<_>, Vec<_>) =
+            files.partition(|f| f.contents_utf8().is_some_and(is_schema));
+        let mut catalog = HashMap::new();
+        for file in others {
+            let (Some(path), Some(text)) = (file.path().to_str(), file.contents_utf8()) else {
+                continue;
+            };
+            if SchemaLanguage::detect(text) == Some(SchemaLanguage::Catalog) {
+                for (id, target) in catalog_entries(path, text) {
+                    catalog.entry(id).or_insert(target);
+                }
+            }
+        }
+        let index = files
+            .iter()
+            .enumerate()
+            .filter_map(|(i, f)| Some((f.path().to_str()?, i)))
+            .collect();
+        let mut graph = Self {
+            files,
+            edges: Vec::new(),
+            missing: Vec::new(),
+            catalog,
+            index,
+        };
+
+        for file in &graph.files {
+            let path = file.path().to_str().unwrap_or_default();
+            let mut targets = Vec::new();
+            let mut unresolved = Vec::new();
+            for location in references(file.contents_utf8().unwrap_or_default()) {
+                // Absolute URLs and URNs without a catalog entry are external
+                let Some(target) = graph.locate(path, location) else {
+                    continue;
+                };
+                match graph.index.get(target.as_str()) {
+                    Some(&i) => targets.push(i),
+                    None => unresolved.push(target),
+                }
+            }
+            targets.sort_unstable();
+            targets.dedup();
+            graph.edges.push(targets);
+            graph.missing.push(unresolved);
+        }
+        graph
+    }
+
+    /// Bundle path a reference from the file at `from` points to, resolving
+    /// relative paths directly and anything else through the catalogs.
+    fn locate(&self, from: &str, location: &str) -> Option<String> {
+        resolve(from, location).or_else(|| self.catalog.get(location).cloned())
+    }
+
+    /// Index of the bundle schema a reference from file `from` points to.
+    pub fn target(&self, from: usize, location: &str) -> Option<usize> {
+        let path = self.files[from].path().to_str()?;
+        self.index
+            .get(self.locate(path, location)?.as_str())
+            .copied()
+    }
+
+    /// Files not referenced by any other file.
+    pub fn entry_points(&self) -> Vec<usize> {
+        let fan_in = self.fan_in();
+        (0..self.files.len()).filter(|&i| fan_in[i] == 0).collect()
+    }
+
+    /// Number of files referencing each file.
+    pub fn fan_in(&self) -> Vec<usize> {
+        let mut fan_in = vec![0; self.files.len()];
+        for (from, targets) in self.edges.iter().enumerate() {
+            for &to in targets.iter().filter(|&&to| to != from) {
+                fan_in[to] += 1;
+            }
+        }
+        fan_in
+    }
+
+    /// The entry point with the largest closure, ties broken by path.
+    pub fn primary(&self) -> Option<usize> {
+        self.entry_points()
+            .into_iter()
+            .map(|i| (self.closure(i).len(), i))
+            .max_by(|(a_len, a), (b_len, b)| {
+                a_len
+                    .cmp(b_len)
+                    .then_with(|| self.files[*b].path().cmp(self.files[*a].path()))
+            })
+            .map(|(_, i)| i)
+    }
+
+    /// The file at `start` plus everything it transitively references, in
+    /// breadth-first order.
+    pub fn closure(&self, start: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.files.len()];
+        let mut order = vec![start];
+        seen[start] = true;
+        let mut i = 0;
+        while i < order.len() {
+            for &next in &self.edges[order[i]] {
+                if !std::mem::replace(&mut seen[next], true) {
+                    order.push(next);
+                }
+            }
+            i += 1;
+        }
+        order
+    }
+
+    /// Reference cycles, each as the files along it starting from the file
+    /// with the smallest path.
+    ///
+    /// Files are visited in path order and each file not already on a reported
+    /// cycle contributes the shortest cycle through it, if any, so every file
+    /// taking part in a cycle appears at least once.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut order: Vec<usize> = (0..self.files.len()).collect();
+        order.sort_by_key(|&i| self.files[i].path());
+        let mut on_cycle = vec![false; self.files.len()];
+        let mut cycles = Vec::new();
+        for start in order {
+            if on_cycle[start] {
+                continue;
+            }
+            let Some(mut cycle) = self.shortest_cycle(start) else {
+                continue;
+            };
+            for &i in &cycle {
+                on_cycle[i] = true;
+            }
+            let first = (0..cycle.len())
+                .min_by_key(|&k| self.files[cycle[k]].path())
+                .unwrap_or_default();
+            cycle.rotate_left(first);
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// Shortest path from `start` back to itself, excluding the repeated end.
+    fn shortest_cycle(&self, start: usize) -> Option<Vec<usize>> {
+        let mut parent = vec![None; self.files.len()];
+        let mut queue = std::collections::VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for &next in &self.edges[node] {
+                if next == start {
+                    let mut cycle = vec![node];
+                    while let Some(p) = parent[*cycle.last()?] {
+                        cycle.push(p);
+                    }
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+                if parent[next].is_none() {
+                    parent[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether the schema rooted at `closure[0]` accepts `root` as the
+    /// document element.
+    ///
+    /// XSD accepts any global element declared in the closure. A RELAX NG
+    /// grammar only accepts the elements its `start` pattern reaches.
+    fn accepts_root(&self, closure: &[usize], root: &DocumentRoot<'_>) -> bool {
+        let text = |i: usize| self.files[i].contents_utf8().unwrap_or_default();
+        let entry = text(closure[0]);
+        if SchemaLanguage::detect(entry) == Some(SchemaLanguage::RelaxNg) {
+            return target_namespace(entry) == root.namespace
+                && start_elements(closure.iter().map(|&i| text(i))).contains(root.local_name);
+        }
+        closure.iter().any(|&i| {
+            target_namespace(text(i)) == root.namespace
+                && declares_root_element(text(i), root.local_name)
+        })
+    }
+
+    /// Total size in bytes of the files in a closure.
+    fn closure_size(&self, closure: &[usize]) -> usize {
+        closure
+            .iter()
+            .map(|&i| self.files[i].contents().len())
+            .sum()
+    }
+
+    /// Find the smallest entry-point closure able to validate a document root.
+    ///
+    /// Closures are compared by file count, then by total size, so of several
+    /// single-file grammars the one with the fewest extras wins.
+    pub fn minimal_closure(&self, root: &DocumentRoot<'_>) -> Option<Vec<usize>> {
+        let mut best = self
+            .entry_points()
+            .into_iter()
+            .map(|entry| self.closure(entry))
+            .filter(|closure| self.accepts_root(closure, root))
+            .min_by_key(|closure| (closure.len(), self.closure_size(closure)))?;
+
+        // Namespaces declared on the root but not covered by the chosen closure
+        for ns in &root.declared {
+            let covered = |closure: &[usize]| {
+                closure.iter().any(|&i| {
+                    target_namespace(self.files[i].contents_utf8().unwrap_or_default()) == Some(ns)
+                })
+            };
+            if covered(&best) {
+                continue;
+            }
+            let extra = (0..self.files.len())
+                .map(|i| self.closure(i))
+                .filter(|closure| covered(closure))
+                .min_by_key(Vec::len);
+            for i in extra.into_iter().flatten() {
+                if !best.contains(&i) {
+                    best.push(i);
+                }
+            }
+        }
+        Some(best)
+    }
+}
+
+/// Generate an OASIS XML catalog for a bundle's schema files.
+///
+/// Each target namespace maps (`uri`) to the file of that namespace with the
+/// largest closure, i.e. its driver module, and every schema file gets a
+/// `system` entry for its relative path.
+pub(crate) fn catalog(graph: &Graph) -> String {
+    let mut namespaces: BTreeMap<&str, (usize, &str)> = BTreeMap::new();
+    let mut systems = Vec::new();
+    for (i, file) in graph.files.iter().enumerate() {
+        let Some(path) = file.path().to_str() else {
+            continue;
+        };
+        systems.push(path);
+        if let Some(ns) = target_namespace(file.contents_utf8().unwrap_or_default()) {
+            let size = graph.closure(i).len();
+            let entry = namespaces.entry(ns).or_insert((size, path));
+            if size > entry.0 || (size == entry.0 && path < entry.1) {
+                *entry = (size, path);
+            }
+        }
+    }
+    systems.sort_unstable();
+
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <catalog xmlns=\"urn:oasis:names:tc:entity:xmlns:xml:catalog\">\n",
+    );
+    for (ns, (_, path)) in namespaces {
+        out.push_str(&format!(
+            "  <uri name=\"{}\" uri=\"{}\"/>\n",
+            escape_attr(ns),
+            escape_attr(path)
+        ));
+    }
+    for path in systems {
+        let path = escape_attr(path);
+        out.push_str(&format!("  <system systemId=\"{path}\" uri=\"{path}\"/>\n"));
+    }
+    out.push_str("</catalog>\n");
+    out
+}
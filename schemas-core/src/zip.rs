@@ -0,0 +1,159 @@
+//! Minimal zip writer backing the `zip` feature.
+//!
+//! Entries are stored uncompressed with UTF-8 names and a fixed timestamp, so
+//! the same input always produces the same archive. Zip64 is not supported;
+//! archives are limited to 65535 entries and 4 GiB, far above any bundle.
+
+use std::io::{self, Seek, Write};
+
+/// Fixed DOS timestamp for every entry: 1980-01-01 00:00:00.
+const DOS_TIME: u16 = 0;
+const DOS_DATE: u16 = (1 << 5) | 1;
+/// General purpose flag marking names as UTF-8.
+const FLAG_UTF8: u16 = 1 << 11;
+const VERSION: u16 = 20;
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// CRC-32 (IEEE) of a byte slice, as used by zip.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &b| {
+        CRC_TABLE[((crc ^ u32::from(b)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+struct Entry {
+    name: String,
+    crc: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// Streaming writer for a stored (uncompressed) zip archive.
+pub(crate) struct ZipWriter<W: Write + Seek> {
+    out: W,
+    /// Stream position of the archive start; zip offsets are relative to it.
+    base: u64,
+    entries: Vec<Entry>,
+}
+
+fn too_large(what: &str) -> io::Error {
+    io::Error::other(format!("{what} exceeds the zip format limits"))
+}
+
+impl<W: Write + Seek> ZipWriter<W> {
+    pub fn new(mut out: W) -> io::Result<Self> {
+        let base = out.stream_position()?;
+        Ok(Self {
+            out,
+            base,
+            entries: Vec::new(),
+        })
+    }
+
+    /// Current offset from the start of the archive.
+    fn position(&mut self) -> io::Result<u64> {
+        Ok(self.out.stream_position()? - self.base)
+    }
+
+    /// Append a file entry.
+    pub fn add(&mut self, name: &str, data: &[u8]) -> io::Result<()> {
+        let offset = u32::try_from(self.position()?).map_err(|_| too_large("archive"))?;
+        let size = u32::try_from(data.len()).map_err(|_| too_large(name))?;
+        let name_len = u16::try_from(name.len()).map_err(|_| too_large(name))?;
+        let crc = crc32(data);
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        header.extend_from_slice(&VERSION.to_le_bytes());
+        header.extend_from_slice(&FLAG_UTF8.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // stored
+        header.extend_from_slice(&DOS_TIME.to_le_bytes());
+        header.extend_from_slice(&DOS_DATE.to_le_bytes());
+        header.extend_from_slice(&crc.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes());
+        header.extend_from_slice(&name_len.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        header.extend_from_slice(name.as_bytes());
+        self.out.write_all(&header)?;
+        self.out.write_all(data)?;
+
+        self.entries.push(Entry {
+            name: name.to_string(),
+            crc,
+            size,
+            offset,
+        });
+        Ok(())
+    }
+
+    /// Write the central directory and return the number of entries.
+    pub fn finish(mut self) -> io::Result<usize> {
+        let start = self.position()?;
+        for entry in &self.entries {
+            let mut record = Vec::with_capacity(46 + entry.name.len());
+            record.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            record.extend_from_slice(&VERSION.to_le_bytes()); // made by
+            record.extend_from_slice(&VERSION.to_le_bytes()); // needed
+            record.extend_from_slice(&FLAG_UTF8.to_le_bytes());
+            record.extend_from_slice(&0u16.to_le_bytes()); // stored
+            record.extend_from_slice(&DOS_TIME.to_le_bytes());
+            record.extend_from_slice(&DOS_DATE.to_le_bytes());
+            record.extend_from_slice(&entry.crc.to_le_bytes());
+            record.extend_from_slice(&entry.size.to_le_bytes());
+            record.extend_from_slice(&entry.size.to_le_bytes());
+            record.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            // Extra field, comment, disk number, internal and external attributes
+            record.extend_from_slice(&[0; 12]);
+            record.extend_from_slice(&entry.offset.to_le_bytes());
+            record.extend_from_slice(entry.name.as_bytes());
+            self.out.write_all(&record)?;
+        }
+        let end = self.position()?;
+
+        let count = u16::try_from(self.entries.len()).map_err(|_| too_large("entry count"))?;
+        let offset = u32::try_from(start).map_err(|_| too_large("archive"))?;
+        let size = u32::try_from(end - start).map_err(|_| too_large("central directory"))?;
+        let mut record = Vec::with_capacity(22);
+        record.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        record.extend_from_slice(&[0; 4]); // disk numbers
+        record.extend_from_slice(&count.to_le_bytes());
+        record.extend_from_slice(&count.to_le_bytes());
+        record.extend_from_slice(&size.to_le_bytes());
+        record.extend_from_slice(&offset.to_le_bytes());
+        record.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        self.out.write_all(&record)?;
+        self.out.flush()?;
+        Ok(self.entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_known_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+}
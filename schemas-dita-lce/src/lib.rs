@@ -54,4 +54,72 @@ mod tests {
         assert_eq!(summary.name, "DITA LCE");
         assert!(summary.file_count > 0);
     }
+
+    #[test]
+    fn test_diff_against_directory() {
+        let base =
+            std::env::temp_dir().join(format!("schemas-dita-lce-diff-{}", std::process::id()));
+        DitaLce::write_to_directory(&base).unwrap();
+        assert!(DitaLce::diff_against_directory(&base).unwrap().is_empty());
+
+        let path = DitaLce::list_paths().next().unwrap();
+        std::fs::write(base.join(path), b"drifted").unwrap();
+        std::fs::write(base.join("extra.xsd"), b"").unwrap();
+        let diff = DitaLce::diff_against_directory(&base).unwrap();
+        std::fs::remove_dir_all(&base).unwrap();
+        assert_eq!(
+            diff.changed,
+            vec![path.to_string_lossy().replace('\\', "/")]
+        );
+        assert_eq!(diff.added, vec!["extra.xsd".to_string()]);
+        assert!(diff.removed.is_empty());
+    }
+
+    #[test]
+    fn test_get_file_str() {
+        let path = DitaLce::list_paths()
+            .find(|p| p.extension().is_some_and(|e| e == "xsd"))
+            .unwrap();
+        let text = DitaLce::get_file_str(path.to_str().unwrap()).unwrap();
+        assert!(text.contains("<xs:schema"));
+
+        assert!(matches!(
+            DitaLce::get_file_str("no/such/file.xsd"),
+            Err(SchemaError::FileNotFound(path)) if path == "no/such/file.xsd"
+        ));
+    }
+
+    #[test]
+    fn test_publish_to_directory() {
+        let parent =
+            std::env::temp_dir().join(format!("schemas-lce-publish-{}", std::process::id()));
+        let published = parent.join("current");
+
+        assert_eq!(
+            DitaLce::publish_to_directory(&published).unwrap(),
+            DitaLce::file_count()
+        );
+        assert!(
+            DitaLce::diff_against_directory(&published)
+                .unwrap()
+                .is_empty()
+        );
+
+        // Republishing replaces the whole tree, dropping stale files
+        std::fs::write(published.join("stale.xsd"), b"").unwrap();
+        DitaLce::publish_to_directory(&published).unwrap();
+        assert!(
+            DitaLce::diff_against_directory(&published)
+                .unwrap()
+                .is_empty()
+        );
+
+        // No temporary or backup directories are left behind
+        let siblings: Vec<_> = std::fs::read_dir(&parent)
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        std::fs::remove_dir_all(&parent).unwrap();
+        assert_eq!(siblings, ["current"]);
+    }
 }
@@ -82,4 +82,89 @@ mod tests {
         assert!(summary.file_count > 0);
         assert!(summary.total_size > 0);
     }
+
+    #[test]
+    fn test_declaration_counts() {
+        let counts = Dita12::declaration_counts();
+        assert!(counts.elements > 0);
+        assert!(counts.attributes > 0);
+        assert!(counts.types > 0);
+    }
+
+    #[test]
+    fn test_directory_sizes() {
+        let sizes = Dita12::directory_sizes();
+        assert_eq!(sizes[""], Dita12::total_size());
+        let base = sizes["xsd1.2/base"];
+        assert!(base > 0 && base < Dita12::total_size());
+        assert!(sizes["xsd1.2/base/xsd"] <= base);
+    }
+
+    #[test]
+    fn test_dependency_degrees() {
+        let degrees = Dita12::dependency_degrees();
+        let find = |path: &str| *degrees.iter().find(|(p, _, _)| *p == path).unwrap();
+
+        // URN references resolve through the bundled catalog
+        let (_, common_in, _) = find("xsd1.2/base/xsd/commonElementMod.xsd");
+        let (_, shell_in, shell_out) = find("xsd1.2/technicalContent/xsd/concept.xsd");
+        assert!(common_in > shell_in);
+        assert_eq!(shell_in, 0);
+        assert!(shell_out > 0);
+    }
+
+    #[test]
+    fn test_include_cycles() {
+        for cycle in Dita12::include_cycles() {
+            assert!(!cycle.is_empty());
+            assert_eq!(cycle.iter().min(), cycle.first());
+            assert!(cycle.iter().all(|path| Dita12::get_file(path).is_some()));
+        }
+    }
+
+    #[test]
+    fn test_files_with_global_element() {
+        let paths: Vec<_> = Dita12::files_with_global_element("map")
+            .into_iter()
+            .map(|f| f.path().to_str().unwrap())
+            .collect();
+        assert!(paths.contains(&"xsd1.2/base/xsd/mapMod.xsd"));
+        assert!(!paths.contains(&"xsd1.2/base/xsd/topicMod.xsd"));
+        assert!(Dita12::files_with_global_element("no-such-element").is_empty());
+    }
+
+    #[test]
+    fn test_files_mentioning() {
+        let found = Dita12::files_mentioning(&["topicref", "shortdesc"]);
+        let names_in = |path: &str| {
+            found
+                .iter()
+                .find(|(p, _)| *p == path)
+                .map(|(_, names)| names.clone())
+                .unwrap_or_default()
+        };
+        assert!(names_in("xsd1.2/base/xsd/mapMod.xsd").contains(&"topicref".to_string()));
+        assert!(names_in("xsd1.2/base/xsd/topicMod.xsd").contains(&"shortdesc".to_string()));
+        assert!(found.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(Dita12::files_mentioning(&["no-such-element"]).is_empty());
+    }
+
+    #[test]
+    fn test_grep_lines() {
+        let path = "xsd1.2/base/xsd/mapMod.xsd";
+        let lines: Vec<_> = Dita12::get_file_str(path).unwrap().lines().collect();
+
+        let hits: Vec<_> = Dita12::grep_lines("xs:element")
+            .filter(|hit| hit.path == path)
+            .collect();
+        assert!(!hits.is_empty());
+        for hit in &hits {
+            assert_eq!(lines[hit.line_number - 1], hit.line);
+            assert!(hit.line.contains("xs:element"));
+        }
+        assert_eq!(
+            hits.len(),
+            lines.iter().filter(|l| l.contains("xs:element")).count()
+        );
+    }
 }
@@ -88,4 +88,55 @@ mod tests {
         assert!(summary.file_count > 0);
         assert!(summary.total_size > 0);
     }
+
+    #[test]
+    fn test_hot_reload_override() {
+        let path = "base/xsd/topicMod.xsd";
+        let embedded = Dita13::get_file(path).unwrap().contents();
+        let dir = std::env::temp_dir().join(format!("schemas-dita13-hot-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("base/xsd")).unwrap();
+        Dita13::set_override_dir(Some(dir.clone()));
+
+        // Files missing from the override directory fall back to the embedded copy
+        assert_eq!(Dita13::get_file(path).unwrap().contents(), embedded);
+
+        std::fs::write(dir.join(path), b"<xs:schema/>").unwrap();
+        assert_eq!(Dita13::get_file(path).unwrap().contents(), b"<xs:schema/>");
+
+        std::fs::write(dir.join(path), b"<xs:schema>changed</xs:schema>").unwrap();
+        assert_eq!(
+            Dita13::get_file(path).unwrap().contents(),
+            b"<xs:schema>changed</xs:schema>"
+        );
+
+        // Absolute paths and `..` segments are rejected outright
+        std::fs::write(dir.join("outside.txt"), b"secret").unwrap();
+        assert!(Dita13::get_file("base/../outside.txt").is_none());
+        assert!(Dita13::get_file(dir.join("outside.txt").to_str().unwrap()).is_none());
+
+        Dita13::set_override_dir(None);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(Dita13::get_file(path).unwrap().contents(), embedded);
+    }
+
+    #[test]
+    fn test_get_files() {
+        let files = Dita13::get_files(&["base/xsd/topicMod.xsd", "missing.xsd", "catalog.xml"]);
+        assert_eq!(files.len(), 3);
+        assert!(files[0].is_some_and(|f| f.path().ends_with("topicMod.xsd")));
+        assert!(files[1].is_none());
+        assert!(files[2].is_some_and(|f| f.path().ends_with("catalog.xml")));
+    }
+
+    #[test]
+    fn test_write_reproducible_tar() {
+        let mut first = Vec::new();
+        let count = Dita13::write_reproducible_tar(&mut first).unwrap();
+        assert_eq!(count, Dita13::file_count());
+
+        let mut second = Vec::new();
+        Dita13::write_reproducible_tar(&mut second).unwrap();
+        assert!(first == second, "tar output differs between runs");
+        assert_eq!(first.len() % 512, 0);
+    }
 }
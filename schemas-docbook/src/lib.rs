@@ -43,6 +43,8 @@ impl SchemaBundle for DocBook51 {
     const NAME: &'static str = "DocBook";
     const VERSION: &'static str = "5.1";
     const LICENSE: &'static str = "BSD-2-Clause";
+    // assembly.rng, dbits.rng and docbookxi.rng are standalone variants
+    const PRIMARY_SCHEMA: Option<&'static str> = Some("rng/docbook.rng");
 
     fn dir() -> &'static Dir<'static> {
         &SCHEMA_DIR
@@ -83,4 +85,103 @@ mod tests {
         assert!(summary.file_count > 0);
         assert!(summary.total_size > 0);
     }
+
+    #[test]
+    fn test_extract_with_catalog() {
+        let base = std::env::temp_dir().join(format!("schemas-docbook-cat-{}", std::process::id()));
+        let (count, catalog) = DocBook51::extract_with_catalog(&base).unwrap();
+        assert_eq!(count, DocBook51::file_count());
+        assert!(base.join("rng/docbook.rng").is_file());
+
+        // The generated catalog must not replace the bundled catalog.xml
+        let bundled = std::fs::read(base.join("catalog.xml")).unwrap();
+        assert_eq!(
+            bundled,
+            DocBook51::get_file("catalog.xml").unwrap().contents()
+        );
+
+        let generated = std::fs::read_to_string(&catalog).unwrap();
+        std::fs::remove_dir_all(&base).unwrap();
+        assert!(generated.contains(r#"uri="rng/docbook.rng""#));
+        assert!(generated.contains(r#"<uri name="http://docbook.org/ns/docbook""#));
+    }
+
+    #[test]
+    fn test_files_by_size_desc() {
+        let ranked = DocBook51::files_by_size_desc();
+        assert_eq!(ranked.len(), DocBook51::file_count());
+        assert_eq!(ranked[0].path(), DocBook51::largest_file().unwrap().path());
+        assert!(
+            ranked
+                .windows(2)
+                .all(|w| w[0].contents().len() >= w[1].contents().len())
+        );
+    }
+
+    #[test]
+    fn test_serve_listener() {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || schemas_core::serve_listener::<DocBook51>(listener));
+
+        let get = |path: &str| {
+            let mut stream = std::net::TcpStream::connect(addr).unwrap();
+            write!(stream, "GET /{path} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+            let mut response = Vec::new();
+            stream.read_to_end(&mut response).unwrap();
+            response
+        };
+
+        let file = DocBook51::files_by_extension("rng").next().unwrap();
+        let response = get(file.path().to_str().unwrap());
+        let split = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+        let head = std::str::from_utf8(&response[..split]).unwrap();
+        assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(head.contains("Content-Type: application/xml\r\n"));
+        assert_eq!(response.len() - split - 4, file.contents().len());
+
+        let response = get("no/such/file.rng");
+        assert!(response.starts_with(b"HTTP/1.1 404 Not Found\r\n"));
+
+        for path in [
+            "%2e%2e/%2e%2e/secret.txt",
+            "..%2Fsecret.txt",
+            "%2Fetc/passwd",
+        ] {
+            assert!(get(path).starts_with(b"HTTP/1.1 404 Not Found\r\n"));
+        }
+    }
+
+    #[test]
+    fn test_primary_extension() {
+        // DocBook ships as many .rnc and .sch files as .rng ones
+        assert_eq!(DocBook51::primary_extension(), Some("rnc"));
+        assert_eq!(DocBook51::files_by_extension("rng").count(), 4);
+    }
+
+    #[test]
+    fn test_primary_schema_report() {
+        let report = DocBook51::primary_schema_report().unwrap();
+        assert_eq!(report.path, "rng/docbook.rng");
+        assert_eq!(report.root_element, "grammar");
+        assert_eq!(
+            report.target_namespace,
+            Some("http://docbook.org/ns/docbook")
+        );
+    }
+
+    #[test]
+    fn test_minimal_schemas_for() {
+        // assembly.rng defines a book pattern too, but its start rejects it
+        let document = br#"<book xmlns="http://docbook.org/ns/docbook"/>"#;
+        let files = DocBook51::minimal_schemas_for(document).unwrap();
+        let paths: Vec<_> = files.iter().map(|f| f.path().to_str().unwrap()).collect();
+        assert_eq!(paths, ["rng/docbook.rng"]);
+
+        let document = br#"<assembly xmlns="http://docbook.org/ns/docbook"/>"#;
+        let files = DocBook51::minimal_schemas_for(document).unwrap();
+        assert_eq!(files[0].path().to_str(), Some("rng/assembly.rng"));
+    }
 }
@@ -52,6 +52,8 @@ impl SchemaBundle for Jats14 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::borrow::Cow;
+    use std::path::Path;
 
     #[test]
     fn test_file_count() {
@@ -82,4 +84,228 @@ mod tests {
         assert!(summary.file_count > 0);
         assert!(summary.total_size > 0);
     }
+
+    #[test]
+    fn test_content_str_ensure_newline() {
+        let path = "standard-modules/mathml3/mathml3.xsd";
+        assert!(!Jats14::get_file(path).unwrap().contents().ends_with(b"\n"));
+        let text = Jats14::content_str_ensure_newline(path).unwrap();
+        assert!(matches!(text, Cow::Owned(_)));
+        assert!(text.ends_with('\n'));
+
+        let text = Jats14::content_str_ensure_newline("standard-modules/xlink.xsd").unwrap();
+        assert!(matches!(text, Cow::Borrowed(_)));
+        assert!(Jats14::content_str_ensure_newline("missing.xsd").is_none());
+    }
+
+    #[test]
+    fn test_detected_version() {
+        let version = Jats14::detected_version("JATS-journalpublishing1-4-mathml3.xsd")
+            .expect("root schema should declare its version");
+        assert_eq!(version, Jats14::VERSION);
+        assert!(Jats14::version_matches_declared());
+    }
+
+    #[test]
+    fn test_minimal_schemas_for() {
+        let document = br#"<?xml version="1.0"?>
+<article xmlns:mml="http://www.w3.org/1998/Math/MathML"
+         xmlns:xlink="http://www.w3.org/1999/xlink">
+  <front/>
+</article>"#;
+        let files = Jats14::minimal_schemas_for(document).unwrap();
+        let paths: Vec<_> = files.iter().map(|f| f.path()).collect();
+        assert!(paths.contains(&Path::new("JATS-journalpublishing1-4-mathml3.xsd")));
+        assert!(files.len() <= Jats14::file_count());
+        for path in paths {
+            assert!(Jats14::get_file(path.to_str().unwrap()).is_some());
+        }
+    }
+
+    #[test]
+    fn test_documentation_block_count() {
+        let count =
+            Jats14::documentation_block_count("JATS-journalpublishing1-4-mathml3-elements.xsd")
+                .unwrap();
+        assert!(count > 0);
+        assert!(Jats14::total_documentation_blocks() >= count);
+        assert!(Jats14::documentation_block_count("missing.xsd").is_none());
+    }
+
+    #[test]
+    fn test_primary_schema_report() {
+        let report = Jats14::primary_schema_report().unwrap();
+        assert_eq!(report.path, "JATS-journalpublishing1-4-mathml3.xsd");
+        assert_eq!(report.root_element, "schema");
+        // JATS elements live in no namespace; the driver only imports namespaced modules
+        assert_eq!(report.target_namespace, None);
+        assert!(
+            report
+                .imported_namespaces
+                .contains(&"http://www.w3.org/1998/Math/MathML")
+        );
+        assert_eq!(
+            Jats14::target_namespace("standard-modules/xlink.xsd"),
+            Some("http://www.w3.org/1999/xlink")
+        );
+    }
+
+    #[test]
+    fn test_files_importing_namespace() {
+        let paths: Vec<_> = Jats14::files_importing_namespace("http://www.w3.org/1999/xlink")
+            .into_iter()
+            .map(|f| f.path().to_str().unwrap())
+            .collect();
+        assert!(paths.contains(&"JATS-journalpublishing1-4-mathml3.xsd"));
+        assert!(Jats14::files_importing_namespace("urn:example:none").is_empty());
+    }
+
+    #[test]
+    fn test_partition_mathml() {
+        let (with, without) = Jats14::partition_mathml();
+        assert!(!with.is_empty());
+        assert!(!without.is_empty());
+        assert_eq!(with.len() + without.len(), Jats14::file_count());
+        assert!(
+            with.iter()
+                .all(|f| !without.iter().any(|g| g.path() == f.path()))
+        );
+    }
+
+    #[test]
+    fn test_primary_extension() {
+        assert_eq!(Jats14::primary_extension(), Some("xsd"));
+    }
+
+    /// Minimal JSON value, enough to walk the tree export.
+    #[derive(Debug)]
+    enum Json {
+        Number(f64),
+        String(String),
+        Array(Vec<Json>),
+        Object(Vec<(String, Json)>),
+    }
+
+    impl Json {
+        fn get(&self, key: &str) -> Option<&Json> {
+            match self {
+                Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+                _ => None,
+            }
+        }
+
+        fn parse(text: &str) -> Json {
+            let (value, rest) = Self::value(text.trim_start());
+            assert!(rest.trim().is_empty());
+            value
+        }
+
+        fn value(text: &str) -> (Json, &str) {
+            if let Some(mut rest) = text.strip_prefix('{') {
+                let mut fields = Vec::new();
+                while !rest.starts_with('}') {
+                    let (Json::String(key), after) = Self::value(rest) else {
+                        panic!("object key must be a string");
+                    };
+                    let (value, after) = Self::value(after.strip_prefix(':').unwrap());
+                    fields.push((key, value));
+                    rest = after.strip_prefix(',').unwrap_or(after);
+                }
+                (Json::Object(fields), &rest[1..])
+            } else if let Some(mut rest) = text.strip_prefix('[') {
+                let mut items = Vec::new();
+                while !rest.starts_with(']') {
+                    let (value, after) = Self::value(rest);
+                    items.push(value);
+                    rest = after.strip_prefix(',').unwrap_or(after);
+                }
+                (Json::Array(items), &rest[1..])
+            } else if let Some(rest) = text.strip_prefix('"') {
+                // Paths in the bundle need no escapes
+                let end = rest.find('"').unwrap();
+                assert!(!rest[..end].contains('\\'));
+                (Json::String(rest[..end].to_string()), &rest[end + 1..])
+            } else {
+                let end = text.find([',', '}', ']']).unwrap_or(text.len());
+                (Json::Number(text[..end].parse().unwrap()), &text[end..])
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_tree_json() {
+        let tree = Json::parse(&Jats14::to_tree_json());
+
+        let mut node = &tree;
+        for name in ["standard-modules", "mathml3", "mathml3.xsd"] {
+            let Some(Json::Array(children)) = node.get("children") else {
+                panic!("expected children");
+            };
+            node = children
+                .iter()
+                .find(|child| matches!(child.get("name"), Some(Json::String(n)) if n == name))
+                .unwrap();
+        }
+
+        let size = Jats14::get_file("standard-modules/mathml3/mathml3.xsd")
+            .unwrap()
+            .contents()
+            .len();
+        assert!(matches!(node.get("size"), Some(Json::Number(n)) if *n == size as f64));
+        assert!(node.get("children").is_none());
+    }
+
+    #[test]
+    fn test_unsupported_namespaces() {
+        let unsupported = Jats14::unsupported_namespaces(&[
+            "http://www.w3.org/1999/xlink",
+            "urn:example:made-up",
+            "http://www.w3.org/1998/Math/MathML",
+            "",
+        ]);
+        assert_eq!(unsupported, ["urn:example:made-up"]);
+    }
+
+    #[test]
+    fn test_mathml_versions() {
+        assert_eq!(
+            Jats14::mathml_versions().into_iter().collect::<Vec<_>>(),
+            ["3.0"]
+        );
+    }
+
+    #[test]
+    fn test_write_flattened_shell() {
+        let base = std::env::temp_dir().join(format!("schemas-jats-flat-{}", std::process::id()));
+        let entry = "JATS-journalpublishing1-4-mathml3.xsd";
+        let count = Jats14::write_flattened_shell(entry, &base).unwrap();
+
+        let written: Vec<String> = std::fs::read_dir(&base)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(written.len(), count);
+        assert!(written.contains(&entry.to_string()));
+        assert!(written.contains(&"mathml3.xsd".to_string()));
+
+        // Every local reference now names a sibling file
+        for name in &written {
+            let text = std::fs::read_to_string(base.join(name)).unwrap();
+            for location in text.split("schemaLocation=\"").skip(1) {
+                let location = &location[..location.find('"').unwrap()];
+                if !location.contains(':') {
+                    assert!(
+                        written.iter().any(|w| w == location),
+                        "{name} -> {location}"
+                    );
+                }
+            }
+        }
+        std::fs::remove_dir_all(&base).unwrap();
+
+        assert!(matches!(
+            Jats14::write_flattened_shell("no-such.xsd", &base),
+            Err(SchemaError::FileNotFound(_))
+        ));
+    }
 }
@@ -93,4 +93,77 @@ mod tests {
         assert_eq!(summary.version, "1.0");
         assert!(summary.file_count > 0);
     }
+
+    #[test]
+    fn test_entries_with_content_type() {
+        let entries: Vec<_> = NisoSts::entries_with_content_type().collect();
+        assert_eq!(entries.len(), NisoSts::file_count());
+        for (path, contents, content_type) in entries {
+            if path.ends_with(".xsd") {
+                assert_eq!(content_type, "application/xml");
+            }
+            assert_eq!(NisoSts::get_file(path).unwrap().contents(), contents);
+        }
+    }
+
+    #[test]
+    fn test_write_to_directory_symlink_dups() {
+        let base = std::env::temp_dir().join(format!("schemas-niso-links-{}", std::process::id()));
+        let count = NisoSts::write_to_directory_symlink_dups(&base).unwrap();
+        assert_eq!(count, NisoSts::file_count());
+
+        // The interchange and extended suites ship identical standard modules
+        let duplicate = base.join("NISO-STS-interchange-1-MathML3-XSD/standard-modules/xml.xsd");
+        #[cfg(unix)]
+        assert!(
+            duplicate
+                .symlink_metadata()
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        for file in NisoSts::files() {
+            assert_eq!(
+                std::fs::read(base.join(file.path())).unwrap(),
+                file.contents()
+            );
+        }
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_relative_paths() {
+        let paths = NisoSts::relative_paths();
+        assert_eq!(paths.len(), NisoSts::file_count());
+        assert!(paths.windows(2).all(|w| w[0] < w[1]));
+        assert!(paths.contains(&std::path::PathBuf::from(
+            "NISO-STS-interchange-1-MathML3-XSD/standard-modules/xml.xsd"
+        )));
+    }
+
+    #[test]
+    fn test_changed_files_since() {
+        let mut baseline: std::collections::BTreeMap<String, [u8; 32]> = NisoSts::list_paths()
+            .map(|p| {
+                let path = p.to_str().unwrap();
+                (path.to_string(), NisoSts::checksum(path).unwrap())
+            })
+            .collect();
+        assert!(NisoSts::changed_files_since(&baseline).is_empty());
+
+        let missing = "NISO-STS-interchange-1-MathML3-XSD/standard-modules/xml.xsd";
+        baseline.remove(missing);
+        let changed: Vec<_> = NisoSts::changed_files_since(&baseline)
+            .into_iter()
+            .map(|f| f.path().to_str().unwrap())
+            .collect();
+        assert_eq!(changed, [missing]);
+    }
+
+    #[test]
+    fn test_mathml_versions() {
+        let versions = NisoSts::mathml_versions();
+        assert!(!versions.is_empty());
+        assert!(versions.contains("3.0"));
+    }
 }
@@ -92,4 +92,90 @@ mod tests {
         assert!(summary.file_count > 0);
         assert!(summary.total_size > 0);
     }
+
+    #[test]
+    fn test_write_to_directory_sync() {
+        let base = std::env::temp_dir().join(format!("schemas-spl-sync-{}", std::process::id()));
+        let first = Spl::write_to_directory_sync(&base).unwrap();
+        assert_eq!(first.written, Spl::file_count());
+
+        let second = Spl::write_to_directory_sync(&base).unwrap();
+        assert_eq!(second.written, 0);
+        assert_eq!(second.skipped, second.total);
+
+        let changed = base.join(Spl::list_paths().next().unwrap());
+        std::fs::write(&changed, b"stale").unwrap();
+        let third = Spl::write_to_directory_sync(&base).unwrap();
+        std::fs::remove_dir_all(&base).unwrap();
+        assert_eq!(third.written, 1);
+    }
+
+    #[test]
+    fn test_files_by_schema_version() {
+        // Only the two HL7 datatypes schemas carry a `version` on xs:schema
+        let files = Spl::files_by_schema_version("R1.1");
+        assert_eq!(files.len(), 2);
+        assert!(Spl::files_by_schema_version("R2b").is_empty());
+    }
+
+    #[test]
+    fn test_xml_declarations() {
+        let declarations = Spl::xml_declarations();
+        assert_eq!(declarations.len(), Spl::file_count());
+
+        let xsd: Vec<_> = declarations
+            .iter()
+            .filter(|(path, _)| path.ends_with(".xsd"))
+            .collect();
+        let declared = xsd.iter().filter(|(_, d)| d.is_some()).count();
+        assert!(declared * 2 > xsd.len());
+        for (_, declaration) in xsd {
+            if let Some(declaration) = declaration {
+                assert!(declaration.starts_with("<?xml") && declaration.ends_with("?>"));
+            }
+        }
+    }
+
+    /// Read the stored entries of a zip archive without a comment.
+    fn unzip(archive: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let u16_at = |i: usize| u16::from_le_bytes([archive[i], archive[i + 1]]) as usize;
+        let u32_at = |i: usize| u32::from_le_bytes(archive[i..i + 4].try_into().unwrap()) as usize;
+
+        let eocd = archive.len() - 22;
+        assert_eq!(u32_at(eocd), 0x0605_4b50);
+        let mut record = u32_at(eocd + 16);
+        let mut entries = Vec::new();
+        for _ in 0..u16_at(eocd + 10) {
+            assert_eq!(u32_at(record), 0x0201_4b50);
+            assert_eq!(u16_at(record + 10), 0, "entries are stored");
+            let size = u32_at(record + 24);
+            let name_len = u16_at(record + 28);
+            let name = &archive[record + 46..record + 46 + name_len];
+            let local = u32_at(record + 42);
+            let data = local + 30 + u16_at(local + 26) + u16_at(local + 28);
+            entries.push((
+                String::from_utf8(name.to_vec()).unwrap(),
+                archive[data..data + size].to_vec(),
+            ));
+            record += 46 + name_len + u16_at(record + 30) + u16_at(record + 32);
+        }
+        entries
+    }
+
+    #[test]
+    fn test_write_validation_package_zip() {
+        let mut archive = std::io::Cursor::new(Vec::new());
+        let count = Spl::write_validation_package_zip(&mut archive).unwrap();
+        assert_eq!(count, Spl::file_count());
+
+        let entries = unzip(archive.get_ref());
+        assert_eq!(entries.len(), count + 1);
+        let (_, schema) = entries.iter().find(|(name, _)| name == "SPL.xsd").unwrap();
+        assert_eq!(schema, Spl::get_file("SPL.xsd").unwrap().contents());
+        let (_, catalog) = entries
+            .iter()
+            .find(|(name, _)| name == "schemas-catalog.xml")
+            .unwrap();
+        assert!(String::from_utf8_lossy(catalog).contains("uri=\"SPL.xsd\""));
+    }
 }
@@ -44,6 +44,9 @@ impl SchemaBundle for TeiP5 {
     const NAME: &'static str = "TEI";
     const VERSION: &'static str = "P5";
     const LICENSE: &'static str = "BSD-2-Clause";
+    // tei_all.xsd imports the TEI examples and xml: namespace modules, which
+    // the TEI generator writes beside it but the P5 distribution does not ship
+    const EXTERNAL_REFERENCES: &'static [&'static str] = &["tei_all_teix.xsd", "tei_all_xml.xsd"];
 
     fn dir() -> &'static Dir<'static> {
         &SCHEMA_DIR
@@ -87,4 +90,56 @@ mod tests {
         assert!(summary.file_count > 0);
         assert!(summary.total_size > 0);
     }
+
+    #[test]
+    fn test_checksum_manifest_filtered() {
+        let base = std::env::temp_dir().join(format!("schemas-tei-sums-{}", std::process::id()));
+        let manifest = TeiP5::write_checksum_manifest_filtered(&base, |f| {
+            f.path().extension().is_some_and(|e| e == "xsd")
+        })
+        .unwrap();
+
+        let contents = std::fs::read_to_string(&manifest).unwrap();
+        std::fs::remove_dir_all(&base).unwrap();
+        assert_eq!(
+            contents.lines().count(),
+            TeiP5::files_by_extension("xsd").count()
+        );
+        assert!(contents.lines().all(|line| line.ends_with(".xsd")));
+        assert!(!contents.contains(".rng"));
+    }
+
+    #[test]
+    fn test_write_to_directory_hashed() {
+        let base = std::env::temp_dir().join(format!("schemas-tei-hashed-{}", std::process::id()));
+        let (count, digest) = TeiP5::write_to_directory_hashed(&base).unwrap();
+
+        assert_eq!(count, TeiP5::file_count());
+        assert_eq!(digest, TeiP5::bundle_digest());
+        for file in TeiP5::files() {
+            assert_eq!(
+                std::fs::read(base.join(file.path())).unwrap(),
+                file.contents()
+            );
+        }
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_write_to_directory_buffered() {
+        let base =
+            std::env::temp_dir().join(format!("schemas-tei-buffered-{}", std::process::id()));
+        let buffered = base.join("buffered");
+        let plain = base.join("plain");
+
+        let count = TeiP5::write_to_directory_buffered(&buffered, 8 * 1024).unwrap();
+        assert_eq!(count, TeiP5::write_to_directory(&plain).unwrap());
+        for file in TeiP5::files() {
+            assert_eq!(
+                std::fs::read(buffered.join(file.path())).unwrap(),
+                std::fs::read(plain.join(file.path())).unwrap()
+            );
+        }
+        std::fs::remove_dir_all(&base).unwrap();
+    }
 }
@@ -17,6 +17,18 @@
 //! - `tei` - TEI P5 (Text Encoding Initiative)
 //! - `spl` - FDA SPL (Pharmaceutical Package Inserts)
 //!
+//! Optional functionality:
+//!
+//! - `checksums` - SHA-256 digests and checksum manifests
+//! - `hot-reload` - Serve files from an on-disk override directory (development only)
+//! - `http-server` - Minimal read-only HTTP server for a bundle (testing only)
+//! - `json` - JSON exports for web clients
+//! - `search` - Plain-text search over file contents
+//! - `sorted-default` - Iterate files in sorted relative-path order
+//! - `tar` - Reproducible tar archive exports
+//! - `xml` - Lightweight inspection of schema contents (versions, namespaces, references)
+//! - `zip` - Zip validation package exports (enables `xml`)
+//!
 //! # Example
 //!
 //! ```toml
@@ -36,11 +48,19 @@
 //! }
 //! ```
 
+mod macros;
+
 // Re-export core types (always available)
 pub use schemas_core::{
-    self as core, BundleSummary, Dir, DirEntry, File, SchemaBundle, SchemaBundleExt, SchemaError,
+    self as core, BundleDiff, BundleSummary, Dir, DirEntry, File, SchemaBundle, SchemaBundleExt,
+    SchemaError, SyncReport, TreeNode,
 };
 
+#[cfg(feature = "search")]
+pub use schemas_core::GrepHit;
+#[cfg(feature = "xml")]
+pub use schemas_core::{DeclarationCounts, PrimarySchemaReport, SchemaLanguage};
+
 // Conditionally re-export schema crates
 #[cfg(feature = "dita")]
 pub use schemas_dita::{self as dita, Dita12};
@@ -72,6 +92,123 @@ pub use schemas_tei::{self as tei, TeiP5};
 #[cfg(feature = "spl")]
 pub use schemas_spl::{self as spl, Spl};
 
+/// Find the enabled bundle able to validate a document.
+///
+/// A bundle with an entry-point schema declaring the document's root element
+/// in its namespace is preferred; otherwise the first bundle whose
+/// [`all_namespaces`](SchemaBundle::all_namespaces) contains the root
+/// namespace is returned. Bundles are tried in feature-list order.
+#[cfg(feature = "xml")]
+pub fn detect_bundle(document: &[u8]) -> Option<BundleSummary> {
+    struct Candidate {
+        summary: fn() -> BundleSummary,
+        declares_root: fn(&[u8]) -> bool,
+        namespaces: fn() -> Vec<&'static str>,
+    }
+
+    // Unused when no bundle feature is enabled
+    #[allow(unused_macros)]
+    macro_rules! candidate {
+        ($bundle:ty) => {
+            Candidate {
+                summary: <$bundle>::summary,
+                declares_root: |document| <$bundle>::minimal_schemas_for(document).is_ok(),
+                namespaces: <$bundle>::all_namespaces,
+            }
+        };
+    }
+
+    let candidates: &[Candidate] = &[
+        #[cfg(feature = "dita")]
+        candidate!(Dita12),
+        #[cfg(feature = "dita13")]
+        candidate!(Dita13),
+        #[cfg(feature = "dita-lce")]
+        candidate!(DitaLce),
+        #[cfg(feature = "niso-sts")]
+        candidate!(NisoSts),
+        #[cfg(feature = "jats")]
+        candidate!(Jats14),
+        #[cfg(feature = "bits")]
+        candidate!(Bits22),
+        #[cfg(feature = "docbook")]
+        candidate!(DocBook51),
+        #[cfg(feature = "akoma-ntoso")]
+        candidate!(AkomaNtoso30),
+        #[cfg(feature = "tei")]
+        candidate!(TeiP5),
+        #[cfg(feature = "spl")]
+        candidate!(Spl),
+    ];
+
+    let namespace = core::root_namespace(document);
+    candidates
+        .iter()
+        .find(|c| (c.declares_root)(document))
+        .or_else(|| {
+            let namespace = namespace?;
+            candidates
+                .iter()
+                .find(|c| (c.namespaces)().contains(&namespace))
+        })
+        .map(|c| (c.summary)())
+}
+
+/// Relative paths whose contents are byte-identical in two or more enabled
+/// bundles, each with the bundles sharing it labelled `"NAME VERSION"`.
+///
+/// Results are sorted by path. A path shipped with different contents by
+/// different groups of bundles is reported once per group. Useful for
+/// deduplicating common files such as `xml.xsd` in a shared cache. Labels
+/// include the version because DITA 1.2 and 1.3 share a `NAME`.
+pub fn common_shared_files() -> Vec<(String, Vec<String>)> {
+    // Never mutated when no bundle feature is enabled
+    #[allow(unused_mut)]
+    let mut groups = SharedGroups::new();
+    for_each_bundle!(|B| {
+        add_shared_candidates::<B>(&mut groups);
+    });
+    shared_files(groups)
+}
+
+/// Bundles shipping each `(path, contents)` pair.
+type SharedGroups = std::collections::BTreeMap<(String, &'static [u8]), Vec<String>>;
+
+// Unused when no bundle feature is enabled
+#[allow(dead_code)]
+fn add_shared_candidates<B: SchemaBundle>(groups: &mut SharedGroups) {
+    let label = format!("{} {}", B::NAME, B::VERSION);
+    for file in B::files() {
+        let key = (file.path().to_string_lossy().into_owned(), file.contents());
+        let bundles = groups.entry(key).or_default();
+        if !bundles.contains(&label) {
+            bundles.push(label.clone());
+        }
+    }
+}
+
+fn shared_files(mut groups: SharedGroups) -> Vec<(String, Vec<String>)> {
+    groups.retain(|_, bundles| bundles.len() > 1);
+    groups
+        .into_iter()
+        .map(|((path, _), bundles)| (path, bundles))
+        .collect()
+}
+
+/// Number of files per license string across the enabled bundles.
+///
+/// Sums each bundle's `file_count` under its `LICENSE`, so bundles sharing a
+/// license are combined.
+pub fn file_counts_by_license() -> std::collections::BTreeMap<String, usize> {
+    // Never mutated when no bundle feature is enabled
+    #[allow(unused_mut)]
+    let mut counts = std::collections::BTreeMap::new();
+    for_each_bundle!(|B| {
+        *counts.entry(B::LICENSE.to_string()).or_default() += B::file_count();
+    });
+    counts
+}
+
 /// Prelude module for convenient imports.
 ///
 /// ```ignore
@@ -155,4 +292,273 @@ mod tests {
         assert!(TeiP5::file_count() > 0);
         assert!(Spl::file_count() > 0);
     }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_no_duplicate_paths() {
+        use crate::prelude::*;
+
+        assert!(Dita12::duplicate_paths().is_empty());
+        assert!(Dita13::duplicate_paths().is_empty());
+        assert!(DitaLce::duplicate_paths().is_empty());
+        assert!(NisoSts::duplicate_paths().is_empty());
+        assert!(Jats14::duplicate_paths().is_empty());
+        assert!(Bits22::duplicate_paths().is_empty());
+        assert!(DocBook51::duplicate_paths().is_empty());
+        assert!(AkomaNtoso30::duplicate_paths().is_empty());
+        assert!(TeiP5::duplicate_paths().is_empty());
+        assert!(Spl::duplicate_paths().is_empty());
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_license_file_path_consistent() {
+        use crate::prelude::*;
+
+        fn check<B: SchemaBundle>() {
+            match B::license_file_path() {
+                Some(path) => {
+                    let file = B::get_file(path).expect("license path does not resolve");
+                    assert_eq!(B::license_text(), file.contents_utf8());
+                }
+                None => assert_eq!(B::license_text(), None),
+            }
+        }
+
+        check::<Dita12>();
+        check::<Dita13>();
+        check::<DitaLce>();
+        check::<NisoSts>();
+        check::<Jats14>();
+        check::<Bits22>();
+        check::<DocBook51>();
+        check::<AkomaNtoso30>();
+        check::<TeiP5>();
+        check::<Spl>();
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_jats_bits_shared_files() {
+        use crate::prelude::*;
+
+        fn entries<B: SchemaBundle>() -> Vec<(&'static str, &'static [u8])> {
+            B::files()
+                .map(|f| (f.path().to_str().unwrap(), f.contents()))
+                .collect()
+        }
+
+        let shared = crate::core::shared_files(&entries::<Jats14>(), &entries::<Bits22>());
+        assert!(shared.iter().any(|p| p.contains("mathml3")));
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_files_unique_to() {
+        use crate::core::files_unique_to;
+
+        let bits_only = files_unique_to::<Bits22, Jats14>();
+        let jats_only = files_unique_to::<Jats14, Bits22>();
+        assert!(!bits_only.is_empty());
+        assert!(!jats_only.is_empty());
+        assert!(bits_only.iter().all(|p| Bits22::get_file(p).is_some()));
+
+        // Together with the shared files, each side covers its whole bundle
+        let shared = crate::core::shared_files(
+            &Jats14::files()
+                .map(|f| (f.path().to_str().unwrap(), f.contents()))
+                .collect::<Vec<_>>(),
+            &Bits22::files()
+                .map(|f| (f.path().to_str().unwrap(), f.contents()))
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(jats_only.len() + shared.len(), Jats14::file_count());
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_for_each_bundle() {
+        use crate::prelude::*;
+
+        let mut total = 0;
+        let mut bundles = 0;
+        crate::for_each_bundle!(|B| {
+            total += B::file_count();
+            bundles += 1;
+        });
+
+        assert_eq!(bundles, 10);
+        assert_eq!(
+            total,
+            Dita12::file_count()
+                + Dita13::file_count()
+                + DitaLce::file_count()
+                + NisoSts::file_count()
+                + Jats14::file_count()
+                + Bits22::file_count()
+                + DocBook51::file_count()
+                + AkomaNtoso30::file_count()
+                + TeiP5::file_count()
+                + Spl::file_count()
+        );
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_file_set_similarity() {
+        use crate::core::file_set_similarity;
+
+        let jats_bits = file_set_similarity::<Jats14, Bits22>();
+        assert!(jats_bits > file_set_similarity::<Jats14, DocBook51>());
+        assert_eq!(jats_bits, file_set_similarity::<Bits22, Jats14>());
+        assert_eq!(file_set_similarity::<Jats14, Jats14>(), 1.0);
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_common_shared_files() {
+        let shared = common_shared_files();
+        assert!(!shared.is_empty());
+        assert!(shared.iter().all(|(_, bundles)| bundles.len() > 1));
+
+        let (_, bundles) = shared
+            .iter()
+            .find(|(path, _)| path == "standard-modules/mathml3/mathml3.xsd")
+            .unwrap();
+        assert_eq!(bundles, &["JATS 1.4", "BITS 2.2"]);
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_common_shared_files_distinguishes_dita_versions() {
+        use crate::prelude::*;
+
+        // DITA 1.2 and 1.3 ship no common files, so stand in DITA 1.3's
+        // files under DITA 1.2's name and version
+        struct Dita13AsDita12;
+        impl SchemaBundle for Dita13AsDita12 {
+            const NAME: &'static str = Dita12::NAME;
+            const VERSION: &'static str = Dita12::VERSION;
+            const LICENSE: &'static str = Dita12::LICENSE;
+
+            fn dir() -> &'static schemas_core::Dir<'static> {
+                Dita13::dir()
+            }
+        }
+
+        let mut groups = SharedGroups::new();
+        add_shared_candidates::<Dita13>(&mut groups);
+        add_shared_candidates::<Dita13AsDita12>(&mut groups);
+        let shared = shared_files(groups);
+        assert_eq!(shared.len(), Dita13::file_count());
+        assert!(
+            shared
+                .iter()
+                .all(|(_, bundles)| bundles == &["DITA 1.3", "DITA 1.2"])
+        );
+    }
+
+    #[cfg(feature = "full")]
+    #[test]
+    fn test_file_counts_by_license() {
+        let counts = file_counts_by_license();
+        let mut total = 0;
+        for_each_bundle!(|B| {
+            total += B::file_count();
+            assert!(counts.contains_key(B::LICENSE));
+        });
+        assert_eq!(counts.values().sum::<usize>(), total);
+    }
+
+    #[cfg(all(feature = "full", feature = "sorted-default"))]
+    #[test]
+    fn test_sorted_default_order() {
+        use crate::prelude::*;
+
+        fn assert_sorted<B: SchemaBundle>() {
+            let paths: Vec<_> = B::list_paths().collect();
+            assert!(
+                paths.windows(2).all(|w| w[0] < w[1]),
+                "{} is not sorted",
+                B::NAME
+            );
+        }
+
+        assert_sorted::<Dita12>();
+        assert_sorted::<Dita13>();
+        assert_sorted::<DitaLce>();
+        assert_sorted::<NisoSts>();
+        assert_sorted::<Jats14>();
+        assert_sorted::<Bits22>();
+        assert_sorted::<DocBook51>();
+        assert_sorted::<AkomaNtoso30>();
+        assert_sorted::<TeiP5>();
+        assert_sorted::<Spl>();
+    }
+
+    #[cfg(all(feature = "full", feature = "hot-reload"))]
+    #[test]
+    fn test_hot_reload_overrides_are_per_bundle() {
+        use crate::prelude::*;
+
+        // DITA 1.2 and 1.3 share a NAME, so this catches keying by name alone
+        let path = "base/xsd/topicMod.xsd";
+        let embedded = Dita13::get_file(path).unwrap().contents();
+        let dir = std::env::temp_dir().join(format!("schemas-hot-dita12-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("base/xsd")).unwrap();
+        std::fs::write(dir.join(path), b"<xs:schema/>").unwrap();
+
+        Dita12::set_override_dir(Some(dir.clone()));
+        assert_eq!(Dita12::get_file(path).unwrap().contents(), b"<xs:schema/>");
+        assert_eq!(Dita13::get_file(path).unwrap().contents(), embedded);
+
+        Dita12::set_override_dir(None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(all(feature = "full", feature = "xml"))]
+    #[test]
+    fn test_no_extension_language_mismatches() {
+        use crate::prelude::*;
+
+        assert!(Dita12::extension_language_mismatches().is_empty());
+        assert!(Dita13::extension_language_mismatches().is_empty());
+        assert!(DitaLce::extension_language_mismatches().is_empty());
+        assert!(NisoSts::extension_language_mismatches().is_empty());
+        assert!(Jats14::extension_language_mismatches().is_empty());
+        assert!(Bits22::extension_language_mismatches().is_empty());
+        assert!(DocBook51::extension_language_mismatches().is_empty());
+        assert!(AkomaNtoso30::extension_language_mismatches().is_empty());
+        assert!(TeiP5::extension_language_mismatches().is_empty());
+        assert!(Spl::extension_language_mismatches().is_empty());
+    }
+
+    #[cfg(all(feature = "full", feature = "xml"))]
+    #[test]
+    fn test_entry_points_verify() {
+        use crate::prelude::*;
+
+        assert_eq!(Dita12::verify_entry_points(), Ok(()));
+        assert_eq!(Dita13::verify_entry_points(), Ok(()));
+        assert_eq!(DitaLce::verify_entry_points(), Ok(()));
+        assert_eq!(NisoSts::verify_entry_points(), Ok(()));
+        assert_eq!(Jats14::verify_entry_points(), Ok(()));
+        assert_eq!(Bits22::verify_entry_points(), Ok(()));
+        assert_eq!(DocBook51::verify_entry_points(), Ok(()));
+        assert_eq!(AkomaNtoso30::verify_entry_points(), Ok(()));
+        assert_eq!(TeiP5::verify_entry_points(), Ok(()));
+        assert_eq!(Spl::verify_entry_points(), Ok(()));
+    }
+
+    #[cfg(all(feature = "full", feature = "xml"))]
+    #[test]
+    fn test_detect_bundle() {
+        let jats = br#"<article xmlns:xlink="http://www.w3.org/1999/xlink" article-type="research-article"/>"#;
+        assert_eq!(detect_bundle(jats).unwrap().name, Jats14::NAME);
+
+        let docbook = br#"<book xmlns="http://docbook.org/ns/docbook" version="5.1"/>"#;
+        assert_eq!(detect_bundle(docbook).unwrap().name, DocBook51::NAME);
+
+        assert!(detect_bundle(br#"<x:record xmlns:x="urn:example:records"/>"#).is_none());
+    }
 }
@@ -0,0 +1,215 @@
+//! The [`for_each_bundle!`](crate::for_each_bundle) macro.
+//!
+//! `cfg` attributes in an exported macro would be evaluated against the
+//! calling crate's features, so each bundle gets a hidden helper macro defined
+//! according to this crate's features instead.
+
+/// Run a block once for every bundle enabled by this crate's features.
+///
+/// The identifier between the bars is bound to each bundle type in turn, in
+/// feature-list order, so generic code runs for all of them:
+///
+/// ```
+/// use schemas::prelude::*;
+///
+/// let mut total = 0;
+/// schemas::for_each_bundle!(|B| {
+///     total += B::file_count();
+/// });
+/// # let _ = total;
+/// ```
+#[macro_export]
+macro_rules! for_each_bundle {
+    (|$bundle:ident| $body:block) => {{
+        $crate::__for_dita!($bundle, $body);
+        $crate::__for_dita13!($bundle, $body);
+        $crate::__for_dita_lce!($bundle, $body);
+        $crate::__for_niso_sts!($bundle, $body);
+        $crate::__for_jats!($bundle, $body);
+        $crate::__for_bits!($bundle, $body);
+        $crate::__for_docbook!($bundle, $body);
+        $crate::__for_akoma_ntoso!($bundle, $body);
+        $crate::__for_tei!($bundle, $body);
+        $crate::__for_spl!($bundle, $body);
+    }};
+}
+
+#[cfg(feature = "dita")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_dita {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::Dita12;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "dita"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_dita {
+    ($bundle:ident, $body:block) => {};
+}
+
+#[cfg(feature = "dita13")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_dita13 {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::Dita13;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "dita13"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_dita13 {
+    ($bundle:ident, $body:block) => {};
+}
+
+#[cfg(feature = "dita-lce")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_dita_lce {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::DitaLce;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "dita-lce"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_dita_lce {
+    ($bundle:ident, $body:block) => {};
+}
+
+#[cfg(feature = "niso-sts")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_niso_sts {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::NisoSts;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "niso-sts"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_niso_sts {
+    ($bundle:ident, $body:block) => {};
+}
+
+#[cfg(feature = "jats")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_jats {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::Jats14;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "jats"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_jats {
+    ($bundle:ident, $body:block) => {};
+}
+
+#[cfg(feature = "bits")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_bits {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::Bits22;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "bits"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_bits {
+    ($bundle:ident, $body:block) => {};
+}
+
+#[cfg(feature = "docbook")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_docbook {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::DocBook51;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "docbook"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_docbook {
+    ($bundle:ident, $body:block) => {};
+}
+
+#[cfg(feature = "akoma-ntoso")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_akoma_ntoso {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::AkomaNtoso30;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "akoma-ntoso"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_akoma_ntoso {
+    ($bundle:ident, $body:block) => {};
+}
+
+#[cfg(feature = "tei")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_tei {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::TeiP5;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "tei"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_tei {
+    ($bundle:ident, $body:block) => {};
+}
+
+#[cfg(feature = "spl")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_spl {
+    ($bundle:ident, $body:block) => {{
+        #[allow(dead_code)]
+        type $bundle = $crate::Spl;
+        $body
+    }};
+}
+
+#[cfg(not(feature = "spl"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __for_spl {
+    ($bundle:ident, $body:block) => {};
+}